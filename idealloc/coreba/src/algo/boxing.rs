@@ -34,31 +34,21 @@ pub fn c_15(
     h:          f64,
     epsilon:    f64,
 ) -> Instance {
-    // Each bucket can be treated independently.
-    // Embarassingly parallel operation. Consolidate
-    // a Mutex-protected Instance.
-    let res = Arc::new(Mutex::new(Instance::new(vec![])));
+    // Each bucket can be treated independently. Embarassingly parallel
+    // operation; every worker boxes its own bucket and the results are
+    // consolidated via a logarithmic-depth pairwise reduction instead of
+    // a single lock all of them would otherwise contend on.
     Instance::make_buckets(input, epsilon)
         .into_par_iter()
-        .for_each(|(h_i, unit_jobs)| {
+        .map(|(h_i, unit_jobs)| {
             debug_assert!(h_i as f64 <= h, "T2 fed with zero H! (ε = {:.2})", epsilon);
             let h_param = (h / h_i as f64).floor() as ByteSteps;
-            let boxed = t_2(unit_jobs, h_param, h as ByteSteps, epsilon, None);
-            let mut guard = res.lock().unwrap();                
-            guard.merge_via_ref(boxed);
-    });
-
-    match Arc::into_inner(res) {
-        Some(v) => {
-            v.into_inner().unwrap()
-        },
-        None    => {
-            // This shouldn't happen because all threads
-            // should have finished by now, and hence `res`
-            // should only have one strong reference.
-            panic!("Could not unwrap Arc!");
-        }
-    }
+            t_2(unit_jobs, h_param, h as ByteSteps, epsilon, None)
+        })
+        .reduce(|| Instance::new(vec![]), |mut a, b| {
+            a.merge_via_ref(b);
+            a
+        })
 }
 
 /// Buchsbaum's Theorem 2.
@@ -128,13 +118,15 @@ fn t_2(
         res_jobs.push(Arc::new(Job::new_box(jobs_buf, h_real)));
     }
 
-    // T2 is going to be called for all X_is in parallel.
-    let res = Arc::new(Mutex::new(Instance::new(res_jobs)));
+    // T2 is going to be called for all X_is in parallel. Every worker
+    // keeps its own recursive result local and the batch is consolidated
+    // by a logarithmic-depth pairwise reduction, rather than `n` workers
+    // serializing on one shared lock for the final merge.
+    let mut res = Instance::new(res_jobs);
 
-    // Missing tasks: (i) set X_i control structures up, do recursion for each
-    // (ii) consolidate Arc-Mutex-protected res.
-    x_is.into_par_iter()
-        .for_each(|(i, x_i)| {
+    // Missing task: set X_i control structures up, do recursion for each.
+    let x_is_merged = x_is.into_par_iter()
+        .map(|(i, x_i)| {
         // We shall be pulling points from this iterator.
         let mut pts_alloc_iter = points_to_allocate.iter().copied().peekable();
 
@@ -181,21 +173,18 @@ fn t_2(
             ) {};
         }
 
-        let x_i_res = t_2(x_i, h, h_real, epsilon, Some(T2Control {
+        t_2(x_i, h, h_real, epsilon, Some(T2Control {
             bounding_interval:  (bi_start, bi_end),
             critical_points:    crit_pts
-        }));
-
-        let mut guard = res.lock().unwrap();
-        guard.merge_via_ref(x_i_res);
+        }))
+    })
+    .reduce(|| Instance::new(vec![]), |mut a, b| {
+        a.merge_via_ref(b);
+        a
     });
 
-    match Arc::into_inner(res) {
-        Some(i)   => {
-            i.into_inner().unwrap()
-        },
-        None  => { panic!("Bad multithreading @ T2!"); }
-    }
+    res.merge_via_ref(x_is_merged);
+    res
 }
 
 /// Implements Buchsbaum et al's Lemma 1.
@@ -263,4 +252,88 @@ fn lemma_1(
     } else {
         (None, input)
     }
+}
+
+/// Assigns deterministic ids to every box in a finished [`Instance`].
+///
+/// `Job::new_box` hands out ids from a single global `AtomicU32`
+/// counting down from `u32::MAX`, so when boxing runs across `c_15`'s
+/// rayon workers, which box gets which id depends on thread
+/// interleaving. Since [`Job`]'s `PartialEq`/`Eq`/`Hash` are all defined
+/// purely on `id`, that makes two runs over identical input produce
+/// plans whose boxes compare and hash differently, which breaks caching
+/// and result comparison downstream.
+///
+/// This renumbers every box (original, leaf jobs are untouched--their
+/// id is part of the user's input, not boxing's output) in the order of
+/// the stable key `(birth, death, minimum original job id contained)`,
+/// making the id space a deterministic function of the input instead of
+/// the scheduler.
+pub fn canonicalize(instance: Instance) -> Instance {
+    let mut keys: Vec<(ByteSteps, ByteSteps, u32, u32)> = vec![];
+    for job in &instance.jobs {
+        collect_box_keys(job, &mut keys);
+    }
+    keys.sort_unstable();
+
+    let mut canonical_ids: HashMap<u32, u32> = HashMap::new();
+    let mut next_id = u32::MAX;
+    for (.., old_id) in keys {
+        canonical_ids.entry(old_id).or_insert_with(|| {
+            let id = next_id;
+            next_id -= 1;
+            id
+        });
+    }
+
+    Instance::new(
+        instance.jobs.iter()
+            .map(|j| renumber(j, &canonical_ids))
+            .collect()
+    )
+}
+
+/// Collects `(birth, death, min_original_id, id)` for `job` and every
+/// box nested within it--the sort key canonicalization orders boxes by.
+fn collect_box_keys(job: &Arc<Job>, keys: &mut Vec<(ByteSteps, ByteSteps, u32, u32)>) {
+    if job.is_original() { return; }
+    keys.push((job.birth, job.death, min_original_id(job), job.id));
+    for child in job.contents.as_ref().unwrap() {
+        collect_box_keys(child, keys);
+    }
+}
+
+/// The smallest original job id reachable from `job`'s subtree.
+fn min_original_id(job: &Job) -> u32 {
+    if job.is_original() {
+        job.id
+    } else {
+        job.contents.as_ref().unwrap()
+            .iter()
+            .map(|j| min_original_id(j))
+            .min()
+            .unwrap_or(u32::MAX)
+    }
+}
+
+/// Rebuilds `job`'s subtree, replacing every box's id with its
+/// canonical one while leaving original jobs untouched.
+fn renumber(job: &Arc<Job>, canonical_ids: &HashMap<u32, u32>) -> Arc<Job> {
+    if job.is_original() {
+        return job.clone();
+    }
+    let contents: JobSet = job.contents.as_ref().unwrap()
+        .iter()
+        .map(|c| renumber(c, canonical_ids))
+        .collect();
+    Arc::new(Job {
+        size:               job.size,
+        birth:              job.birth,
+        death:              job.death,
+        req_size:           job.req_size,
+        alignment:          job.alignment,
+        contents:           Some(contents),
+        originals_boxed:    job.originals_boxed,
+        id:                 canonical_ids[&job.id],
+    })
 }
\ No newline at end of file
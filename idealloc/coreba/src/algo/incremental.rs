@@ -0,0 +1,179 @@
+use crate::{
+    helpe::*,
+    algo::placement::do_best_fit,
+    analyze::prelude_analysis,
+};
+
+/// Re-places `new_input`, biasing the result towards `previous`'s
+/// offsets so that buffers which persist across successive calls don't
+/// jump to new addresses--and so force an expensive copy--for no reason
+/// beyond which of several equally valid placements happened to be
+/// picked this time around.
+///
+/// Only [`AnalysisResult::SameSizes`] has that freedom to exploit: its
+/// rows (built by `interval_graph_coloring`) are interchangeable, since
+/// every row already satisfies the interference constraints on its own
+/// and any permutation of row indices is validity-preserving. We pick
+/// the permutation that keeps the most total bytes at their old offset,
+/// via a min-cost bipartite matching between this call's rows and the
+/// row-indices `previous` used, solved with the textbook successive-
+/// shortest-augmenting-path algorithm under Johnson/Dijkstra potentials.
+///
+/// Every other [`AnalysisResult`] already has exactly one sensible
+/// placement (the same offset for all non-overlapping jobs, or whatever
+/// the probabilistic BA search lands on)--there's no freedom to bias, so
+/// those fall back to the regular [`super::idealloc`] pipeline.
+pub fn idealloc_incremental(
+    previous:           &PlacedJobRegistry,
+    new_input:          JobSet,
+    worst_case_frag:    f64,
+    start_address:      ByteSteps,
+    max_lives:          u32,
+    batch_width:        u32,
+) -> (PlacedJobRegistry, ByteSteps) {
+    match prelude_analysis(new_input.clone()) {
+        AnalysisResult::SameSizes(jobs, ig, reg) => {
+            relocate_minimizing(jobs, ig, reg, previous, start_address)
+        },
+        _ => super::idealloc(
+            new_input,
+            worst_case_frag,
+            start_address,
+            max_lives,
+            batch_width,
+            #[cfg(all(feature = "telemetry", feature = "std"))]
+            None,
+        ),
+    }
+}
+
+/// Assigns each `SameSizes` row the row-index that minimizes total
+/// relocated bytes against `previous`, then feeds the result into the
+/// existing `do_best_fit` compaction so new/removed buffers are
+/// absorbed exactly as the non-incremental `SameSizes` path does.
+///
+/// This assumes `previous` was itself produced from a `SameSizes`
+/// placement at the same row size--i.e. jobs shared between the two
+/// calls didn't change size--since a previous offset is only meaningful
+/// as a row index once divided by `row_size`.
+fn relocate_minimizing(
+    jobs:           JobSet,
+    ig:             InterferenceGraph,
+    reg:            PlacedJobRegistry,
+    previous:       &PlacedJobRegistry,
+    start_address:  ByteSteps,
+) -> (PlacedJobRegistry, ByteSteps) {
+    let row_size = jobs[0].size;
+    let rows = interval_graph_coloring(jobs);
+
+    // Pad the smaller side with zero-cost dummies: a previously-unused
+    // row index costs nothing to adopt, and a row-index slot with no
+    // row to fill it this round is simply left empty.
+    let n = rows.len().max(
+        previous.values()
+            .map(|pj| pj.offset.get() / row_size + 1)
+            .max()
+            .unwrap_or(0)
+    );
+
+    // cost[i][j] = bytes that would move if row `i` is placed at
+    // row-index `j`. Jobs new to this call (absent from `previous`)
+    // contribute nothing no matter where they land.
+    let mut cost = vec![vec![0i64; n]; n];
+    for (i, row) in rows.iter().enumerate() {
+        for job in row {
+            if let Some(prev) = previous.get(&job.id) {
+                let prev_row = prev.offset.get() / row_size;
+                for (j, c) in cost[i].iter_mut().enumerate() {
+                    if j != prev_row {
+                        *c += job.size as i64;
+                    }
+                }
+            }
+        }
+    }
+
+    let assignment = min_cost_assignment(&cost);
+
+    let mut loose: LoosePlacement = BinaryHeap::new();
+    for (i, row) in rows.into_iter().enumerate() {
+        let target_offset = assignment[i] * row_size;
+        for job in row {
+            let semi_placed = reg.get(&job.id).unwrap();
+            semi_placed.offset.set(target_offset);
+            loose.push(semi_placed.clone());
+        }
+    }
+
+    (reg, do_best_fit(loose, &ig, 0, ByteSteps::MAX, false, start_address))
+}
+
+/// Solves the balanced assignment problem--minimum-cost perfect matching
+/// on a complete bipartite graph--via successive shortest augmenting
+/// paths under Johnson/Dijkstra potentials (the textbook O(n^3) Hungarian
+/// algorithm). `cost` must be square; returns, for each row, the column
+/// index it was matched to.
+fn min_cost_assignment(cost: &[Vec<i64>]) -> Vec<usize> {
+    let n = cost.len();
+    if n == 0 { return vec![]; }
+    const INF: i64 = i64::MAX / 2;
+
+    // 1-indexed, as in the classical presentation: `p[j]` is the row
+    // matched to column `j` (0 = unmatched sentinel), `u`/`v` are the
+    // row/column potentials kept such that every reduced cost stays
+    // non-negative, so Dijkstra can be run without negative edges.
+    let mut u = vec![0i64; n + 1];
+    let mut v = vec![0i64; n + 1];
+    let mut p = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut min_v = vec![INF; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = INF;
+            let mut j1 = 0;
+            for j in 1..=n {
+                if !used[j] {
+                    let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                    if cur < min_v[j] {
+                        min_v[j] = cur;
+                        way[j] = j0;
+                    }
+                    if min_v[j] < delta {
+                        delta = min_v[j];
+                        j1 = j;
+                    }
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_v[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 { break; }
+        }
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 { break; }
+        }
+    }
+
+    let mut assignment = vec![0usize; n];
+    for j in 1..=n {
+        if p[j] != 0 {
+            assignment[p[j] - 1] = j - 1;
+        }
+    }
+    assignment
+}
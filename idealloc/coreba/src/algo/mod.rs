@@ -1,5 +1,6 @@
 pub mod boxing;
 pub mod placement;
+pub mod incremental;
 
 use placement::do_best_fit;
 
@@ -12,8 +13,11 @@ use crate::{
 };
 use self::boxing::{
     c_15,
+    canonicalize,
     rogue,
 };
+#[cfg(all(feature = "telemetry", feature = "std"))]
+use crate::telemetry::{IterationMetrics, TelemetrySink};
 
 /// Assigns proper offsets to each buffer in `JobSet`,
 /// so that the resulting memory fragmentation is at
@@ -23,11 +27,13 @@ use self::boxing::{
 /// 
 /// `idealloc` is, in its non-trivial case, probabilistic.
 /// It tries different placements again and again in a loop
-/// and picks the best one. This constant controls the maximum
+/// and picks the best one. `max_lives` controls the maximum
 /// number of iterations allowed to `idealloc` to outperform its
 /// last best placement. The *total* number of iterations is
-/// thus stochastic.
-/// 
+/// thus stochastic. `batch_width` groups those tries into waves
+/// of independent trials run across Rayon's pool, instead of one
+/// at a time.
+///
 /// Returns the placement itself, and the corresponding
 /// makespan. If worst-case-fragmentation was exceeded,
 /// the immediately next best achieved placement is returned.
@@ -36,6 +42,19 @@ pub fn idealloc(
     worst_case_frag:    f64,
     start_address:      ByteSteps,
     max_lives:          u32,
+    // Width of each parallel wave of independent BA trials--see the
+    // `NeedsBA` arm below. Values `<= 1` run one trial per wave, so the
+    // search stops at the first trial that fails to improve on the best
+    // placement so far.
+    batch_width:        u32,
+    // Opt-in CSV telemetry of the boxing/squeezing loop below--`None`
+    // if the caller doesn't want it. One row is written for the first
+    // try, then one per wave that improves on `best_opt` (for its winning
+    // trial); the final, non-improving wave and the other trials of each
+    // wave get no row. Pays nothing at all when the `telemetry` feature
+    // is off.
+    #[cfg(all(feature = "telemetry", feature = "std"))]
+    mut telemetry:      Option<TelemetrySink>,
 ) -> (PlacedJobRegistry, ByteSteps) {
     // Set a big enough stack size, since core algo is recursive.
     if let Ok(_) = rayon::ThreadPoolBuilder::new().stack_size(1048576 * 1024).build_global() {}
@@ -92,7 +111,7 @@ pub fn idealloc(
         },
         AnalysisResult::NeedsBA(BACtrl {
             input,
-            mut pre_boxed,
+            pre_boxed,
             to_box,
             epsilon,
             real_load,
@@ -122,6 +141,12 @@ pub fn idealloc(
                     baby.offset.set(pj.offset.get());
                     (baby.descr.id, Rc::new(baby))})
                 .collect();
+            // The original (unboxed) jobs, kept around as plain `Arc<Job>`s
+            // so that parallel trials below can each rebuild their own
+            // `Instance`/registry from `Send`+`Sync` data, instead of
+            // having to move `ig_reg`'s `Rc`/`Cell`-based one across
+            // threads.
+            let original_jobs: JobSet = reg.values().map(|pj| pj.descr.clone()).collect();
             let ig_reg = (ig, reg);
 
             // Initializations related to the last
@@ -133,8 +158,14 @@ pub fn idealloc(
             let (_h_min, h_max) = input.min_max_height();
             let final_h = h_max as f64 / mu;
 
-            while lives_left > 0 && best_opt > target_opt {
-                let boxed = c_15(pre_boxed.clone(), final_h, mu);
+            #[cfg(all(feature = "telemetry", feature = "std"))]
+            if let Some(t) = telemetry.as_mut() { t.tick(); }
+
+            // The very first try reuses the `pre_boxed` that
+            // `prelude_analysis` already built--there's no `rogue` call
+            // here yet, so nothing to parallelize.
+            if lives_left > 0 && best_opt > target_opt {
+                let boxed = canonicalize(c_15(pre_boxed, final_h, mu));
                 debug_assert!(boxed.check_boxed_originals(to_box as u32), "Invalid boxing!");
                 let current_opt = boxed.place(&ig_reg, total_iters, best_opt, dumb_id, start_address);
                 debug_assert!(current_opt == ByteSteps::MAX || current_opt >= real_load, "Bad placement");
@@ -150,11 +181,118 @@ pub fn idealloc(
                             (baby.descr.id, Rc::new(baby))})
                         .collect();
                 }
+                #[cfg(all(feature = "telemetry", feature = "std"))]
+                if let Some(t) = telemetry.as_mut() {
+                    let times_resqueezed = ig_reg.1
+                        .values()
+                        .filter(|pj| pj.times_squeezed.get() == total_iters + 1)
+                        .count();
+                    let elapsed_micros = t.tick().as_micros();
+                    let _ = t.record(&IterationMetrics {
+                        iteration: total_iters,
+                        elapsed_micros,
+                        best_opt,
+                        real_load,
+                        times_resqueezed,
+                        epsilon,
+                        mu_lim,
+                    });
+                }
                 total_iters += 1;
                 lives_left -= 1;
-                if lives_left > 0 && best_opt > target_opt {
-                    pre_boxed = rogue(input.clone(), epsilon);
-                } else { break; }
+            }
+
+            // Every subsequent try needs its own `rogue(input.clone(), epsilon)`
+            // call--and `input` is `Rc`-based, so it can't be moved into
+            // another thread. We run these tries in waves of up to
+            // `batch_width` across Rayon's work-stealing pool instead:
+            // each worker rebuilds its own `Instance` (from `original_jobs`,
+            // which is `Arc`-backed and thus safely shareable) and its own
+            // `InterferenceGraph`/`PlacedJobRegistry` (via `build_ig_registry`,
+            // mirroring `bin/heuristic.rs`'s precedent for the same
+            // `Rc`/`Cell`-across-threads problem), so concurrent trials
+            // never stomp each other's offsets. A wave stops the search
+            // early the moment it fails to improve on `best_opt`.
+            //
+            // A trial's own `(InterferenceGraph, PlacedJobRegistry)` is
+            // `Rc`-based and thus `!Send`--it can't be the thing `collect()`
+            // hands back across the `into_par_iter()` boundary. Each trial
+            // therefore extracts only the `Send` bits it needs to report--the
+            // makespan and the winning offset of every original job--and the
+            // wave winner's registry is rebuilt from scratch on this thread.
+            'waves: while lives_left > 0 && best_opt > target_opt {
+                let batch = batch_width.max(1).min(lives_left);
+                let base_iter = total_iters;
+                let wave: Vec<(ByteSteps, u32, Vec<(u32, ByteSteps)>, usize)> = (0..batch)
+                    .into_par_iter()
+                    .map(|i| {
+                        // `input` also holds the dummy job (if any), which
+                        // `to_box` counts--so must each trial's copy.
+                        let trial_input = Rc::new(Instance::new(
+                            original_jobs.iter().cloned().chain(dummy.iter().cloned()).collect()
+                        ));
+                        let trial_pre_boxed = rogue(trial_input, epsilon);
+                        let boxed = canonicalize(c_15(trial_pre_boxed, final_h, mu));
+                        debug_assert!(boxed.check_boxed_originals(to_box as u32), "Invalid boxing!");
+                        let trial_ig_reg = build_ig_registry(&original_jobs);
+                        let trial_iter = base_iter + i;
+                        let current_opt = boxed.place(&trial_ig_reg, trial_iter, best_opt, dumb_id, start_address);
+                        debug_assert!(current_opt == ByteSteps::MAX || current_opt >= real_load, "Bad placement");
+                        let times_resqueezed = trial_ig_reg.1
+                            .values()
+                            .filter(|pj| pj.times_squeezed.get() == trial_iter + 1)
+                            .count();
+                        let offsets: Vec<(u32, ByteSteps)> = trial_ig_reg.1
+                            .values()
+                            .map(|pj| (pj.descr.id, pj.offset.get()))
+                            .collect();
+                        (current_opt, trial_iter, offsets, times_resqueezed)
+                    })
+                    .collect();
+
+                let wave_best = wave.into_iter().min_by_key(|(opt, ..)| *opt);
+                total_iters = base_iter + batch;
+                lives_left -= batch;
+
+                if let Some((wave_opt, wave_iter, wave_offsets, times_resqueezed)) = wave_best {
+                    if wave_opt < best_opt {
+                        // The winning trial's registry never left its worker
+                        // thread--rebuild a fresh one here and replay its
+                        // offsets onto it.
+                        let wave_ig_reg = build_ig_registry(&original_jobs);
+                        for (id, offset) in &wave_offsets {
+                            wave_ig_reg.1.get(id).unwrap().offset.set(*offset);
+                        }
+                        debug_assert!(placement_is_valid(&wave_ig_reg));
+                        best_opt = wave_opt;
+                        println!("Beating heuristic by {} bytes! ({wave_iter} iterations)", heuristic_opt - best_opt);
+                        final_placement = wave_ig_reg.1
+                            .values()
+                            .map(|pj| {
+                                let baby = PlacedJob::new(pj.descr.clone());
+                                baby.offset.set(pj.offset.get());
+                                (baby.descr.id, Rc::new(baby))})
+                            .collect();
+                        #[cfg(all(feature = "telemetry", feature = "std"))]
+                        if let Some(t) = telemetry.as_mut() {
+                            let elapsed_micros = t.tick().as_micros();
+                            let _ = t.record(&IterationMetrics {
+                                iteration: wave_iter,
+                                elapsed_micros,
+                                best_opt,
+                                real_load,
+                                times_resqueezed,
+                                epsilon,
+                                mu_lim,
+                            });
+                        }
+                        continue 'waves;
+                    }
+                }
+                // No trial in this wave improved on `best_opt`: further
+                // waves would just be repeating the same probabilistic
+                // search with no reason to expect better luck.
+                break;
             };
 
             let num_buffers = ig_reg.1.len();
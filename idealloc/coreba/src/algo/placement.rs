@@ -121,7 +121,7 @@ pub fn get_loose_placement(
 
 /// Performs best/first-fit placement of an already-ordered collection
 /// of jobs (by some symbolic offset). Returns the resulting makespan.
-/// 
+///
 /// Stops early if the running makespan exceeds a pre-defined limit.
 pub fn do_best_fit(
     mut loose:      LoosePlacement,
@@ -132,20 +132,31 @@ pub fn do_best_fit(
     start_addr:     ByteSteps,
 ) -> ByteSteps {
     let mut max_address = 0;
+    // Spatial index of jobs already squeezed during THIS call, so
+    // that candidates are found via a vertical-strip (time-range)
+    // query; the job's interference-graph row is still read below to
+    // keep only its neighbors squeezed during this call.
+    let mut index: PlacedJobIndex = PlacedJobIndex::new();
     // Traverse loosely placed jobs in ascending offset.
     while let Some(to_squeeze) = loose.pop() {
         let min_gap_size = to_squeeze.descr.size;
         let mut offset_runner = 0;
         let mut smallest_gap = ByteSteps::MAX;
         let mut best_offset: Option<ByteSteps> = None;
+        // Only the jobs actually squeezed during this call are
+        // legitimate candidates.
+        let squeezed_this_iter: HashSet<u32> = ig.get(&to_squeeze.descr.id)
+            .unwrap()
+            .iter()
+            .filter(|j| { j.times_squeezed.get() == iters_done + 1 })
+            .map(|j| j.descr.id)
+            .collect();
         // Traverse already-squeezed jobs that overlap with
         // the current one in ascending offset. You're looking
         // for the smallest gap which fits the job, alignment
         // requirements included.
-        let mut jobs_vec = ig.get(&to_squeeze.descr.id)
-            .unwrap()
-            .iter()
-            .filter(|j| { j.times_squeezed.get() == iters_done + 1 })
+        let mut jobs_vec = query_time_overlap(&index, &to_squeeze)
+            .filter(|j| { squeezed_this_iter.contains(&j.descr.id) })
             .sorted_unstable()
             .rev()
             .peekable();
@@ -183,6 +194,7 @@ pub fn do_best_fit(
                 return ByteSteps::MAX;
             }
         }
+        index.insert(PlacedBox { job: to_squeeze });
     };
 
     max_address
@@ -114,15 +114,25 @@ pub fn prelude_analysis(mut jobs: JobSet) -> AnalysisResult {
         // We have observed a tendency to underperform against the following
         // heuristic--we thus keep it as a fallback solution.
         //
-        // It's "sort by size-and-lifetime and do first-fit".
-        let ordered: PlacedJobSet = registry.values()
-            .sorted_by(|a, b| { 
-                b.descr
-                    .size
-                    .cmp(&a.descr.size)
-                    .then(b.descr.lifetime().cmp(&a.descr.lifetime()))
-                })
-            .cloned()
+        // It's "sort by size-and-lifetime and do first-fit". Sorted in
+        // parallel: `Rc<PlacedJob>` isn't `Send`, so we sort plain
+        // `(size, lifetime, id, index)` keys--which are--and use the
+        // winning permutation to reorder the actual jobs afterward.
+        // `registry` is a `HashMap`, so `base`'s order is randomized per
+        // process; `par_sort_by` is stable, but that only preserves
+        // whatever order `base` happened to come in, so ties still need
+        // their own input-derived tiebreak--`pj.descr.id`, unique per
+        // job--for the ordering to be deterministic and reproducible.
+        let base: PlacedJobSet = registry.values().cloned().collect();
+        let mut keys: Vec<(ByteSteps, ByteSteps, u32, usize)> = base.iter()
+            .enumerate()
+            .map(|(i, pj)| (pj.descr.size, pj.descr.lifetime(), pj.descr.id, i))
+            .collect();
+        keys.par_sort_by(|(a_size, a_life, a_id, _), (b_size, b_life, b_id, _)| {
+            b_size.cmp(a_size).then(b_life.cmp(a_life)).then(a_id.cmp(b_id))
+        });
+        let ordered: PlacedJobSet = keys.into_iter()
+            .map(|(.., i)| base[i].clone())
             .collect();
         println!("Size-life ordering done.");
         let mut symbolic_offset = 0;
@@ -156,20 +166,24 @@ pub fn prelude_analysis(mut jobs: JobSet) -> AnalysisResult {
         // Instance characterization.
         let h_mean = sizes_sum as f64 / to_box as f64;
         let death_mean = deaths_sum as f64 / to_box as f64;
-        let (height_squared_devs, death_squared_devs) = jobs.iter()
-            .fold((0.0, 0.0), |(ss, ls), j| {
+        let (height_squared_devs, death_squared_devs) = jobs.par_iter()
+            .fold(|| (0.0, 0.0), |(ss, ls), j| {
                 (
                     ss + (j.size as f64 - h_mean).powi(2),
                     ls + (j.death as f64 - death_mean).powi(2)
                 )
 
-            });
+            })
+            .reduce(|| (0.0, 0.0), |(ss1, ls1), (ss2, ls2)| (ss1 + ss2, ls1 + ls2));
         let size_std = (height_squared_devs / (to_box as f64)).sqrt();
         let death_std = (death_squared_devs / (to_box as f64)).sqrt();
         let h_hardness = size_std / h_mean;
         let death_hardness = death_std / death_mean;
-        let double_num_conflicts = ig.values()
-            .fold(0, |s, js| s + js.len());
+        // `ig`'s rows are `Rc<PlacedJob>`-based, so not `Send`--only the
+        // (already cheap, per-row) lengths are pulled out sequentially;
+        // summing them is the part actually worth doing in parallel.
+        let row_lens: Vec<usize> = ig.values().map(|js| js.len()).collect();
+        let double_num_conflicts = row_lens.par_iter().sum::<usize>();
         assert!(double_num_conflicts % 2 == 0);
         let num_two_combos = to_box * (to_box - 1) / 2;
         let conflict_hardness = (double_num_conflicts / 2) as f64 / num_two_combos as f64;
@@ -266,4 +280,146 @@ pub fn placement_is_valid(ig_reg: &(InterferenceGraph, PlacedJobRegistry)) -> bo
     }
 
     true
+}
+
+/// A broken dynamic-storage-allocation invariant, naming exactly which
+/// jobs or box violated it so regressions in `lemma_1`/`t_2`'s strip
+/// cutting surface immediately instead of as silent overlaps.
+#[derive(Error, Debug)]
+pub enum InvariantViolation {
+    #[error("jobs {a} and {b} are both live at t={at} but overlap in [{a_off}, {a_end}) vs [{b_off}, {b_end})")]
+    OffsetOverlap {
+        a:      u32,
+        b:      u32,
+        at:     ByteSteps,
+        a_off:  ByteSteps,
+        a_end:  ByteSteps,
+        b_off:  ByteSteps,
+        b_end:  ByteSteps,
+    },
+    #[error("box {box_id} has height {height} but its contents need {load}")]
+    BoxTooShort {
+        box_id: u32,
+        height: ByteSteps,
+        load:   ByteSteps,
+    },
+    #[error("box {box_id} spans [{actual_birth}, {actual_death}) but its contents span [{expected_birth}, {expected_death})")]
+    BadSpan {
+        box_id:         u32,
+        expected_birth: ByteSteps,
+        expected_death: ByteSteps,
+        actual_birth:   ByteSteps,
+        actual_death:   ByteSteps,
+    },
+    #[error("box {box_id} claims {claimed} originals boxed, but {recounted} were found")]
+    BadOriginalsCount {
+        box_id:     u32,
+        claimed:    u32,
+        recounted:  u32,
+    },
+    #[error("job {id} has no materialized offset")]
+    MissingOffset { id: u32 },
+    #[error("job {id} requires alignment {alignment} but got offset {offset}")]
+    Misaligned {
+        id:         u32,
+        offset:     ByteSteps,
+        alignment:  ByteSteps,
+    },
+}
+
+/// Mechanically proves the fundamental DSA invariants hold over a
+/// completed box hierarchy plus its materialized per-job offsets (e.g.
+/// the output of [`crate::materialize::materialize_offsets`]):
+///
+/// 1. every pair of original jobs whose open lifetimes overlap got
+///    disjoint `[offset, offset + size)` ranges;
+/// 2. every box's height is at least the load of its contents;
+/// 3. every box's `(birth, death)` is exactly the min-birth/max-death
+///    over its contents, and its `originals_boxed` count matches a
+///    fresh recount;
+/// 4. every original job with a requested [`Job::alignment`] got an
+///    offset that's a multiple of it.
+///
+/// Intended for `debug_assert!` paths and integration tests--walking
+/// the whole hierarchy on every run would be wasteful in release builds.
+pub fn validate_dsa_invariants(
+    root:       &Job,
+    offsets:    &HashMap<u32, ByteSteps>,
+) -> Result<(), InvariantViolation> {
+    validate_box(root)?;
+
+    let mut originals: Vec<&Job> = vec![];
+    collect_originals(root, &mut originals);
+    for o in &originals {
+        let off = *offsets.get(&o.id).ok_or(InvariantViolation::MissingOffset { id: o.id })?;
+        if let Some(alignment) = o.get_alignment() {
+            if alignment > 0 && off % alignment != 0 {
+                return Err(InvariantViolation::Misaligned { id: o.id, offset: off, alignment });
+            }
+        }
+    }
+    for (i, a) in originals.iter().enumerate() {
+        let a_off = *offsets.get(&a.id).ok_or(InvariantViolation::MissingOffset { id: a.id })?;
+        let a_end = a_off + a.size;
+        for b in &originals[i + 1..] {
+            if a.birth >= b.death || b.birth >= a.death { continue; }
+            let b_off = *offsets.get(&b.id).ok_or(InvariantViolation::MissingOffset { id: b.id })?;
+            let b_end = b_off + b.size;
+            if a_off < b_end && b_off < a_end {
+                return Err(InvariantViolation::OffsetOverlap {
+                    a:      a.id,
+                    b:      b.id,
+                    at:     a.birth.max(b.birth) + 1,
+                    a_off, a_end, b_off, b_end,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_box(job: &Job) -> Result<(), InvariantViolation> {
+    let Some(contents) = job.contents.as_ref() else { return Ok(()); };
+
+    let load = get_load(contents);
+    if load > job.size {
+        return Err(InvariantViolation::BoxTooShort { box_id: job.id, height: job.size, load });
+    }
+
+    let (mut expected_birth, mut expected_death) = (ByteSteps::MAX, 0);
+    let mut recounted = 0;
+    for c in contents {
+        expected_birth = expected_birth.min(c.birth);
+        expected_death = expected_death.max(c.death);
+        recounted += if c.is_original() { 1 } else { c.originals_boxed };
+    }
+    if expected_birth != job.birth || expected_death != job.death {
+        return Err(InvariantViolation::BadSpan {
+            box_id:         job.id,
+            expected_birth,
+            expected_death,
+            actual_birth:   job.birth,
+            actual_death:   job.death,
+        });
+    }
+    if recounted != job.originals_boxed {
+        return Err(InvariantViolation::BadOriginalsCount {
+            box_id:     job.id,
+            claimed:    job.originals_boxed,
+            recounted,
+        });
+    }
+
+    for c in contents {
+        validate_box(c)?;
+    }
+    Ok(())
+}
+
+fn collect_originals<'a>(job: &'a Job, out: &mut Vec<&'a Job>) {
+    match &job.contents {
+        None            => out.push(job),
+        Some(contents)  => contents.iter().for_each(|c| collect_originals(c, out)),
+    }
 }
\ No newline at end of file
@@ -0,0 +1,164 @@
+use crate::helpe::*;
+use core::alloc::Layout;
+
+/// Replays a finished `idealloc` placement as a live allocator.
+///
+/// A [PlacedArena] is built once, from the [PlacedJobRegistry] a run of
+/// `idealloc` left behind and a backing buffer exactly `best_opt` bytes
+/// wide. From then on every [`alloc`](PlacedArena::alloc) call is matched,
+/// in birth order, to the next scheduled [PlacedJob] and simply hands back
+/// that job's already-computed offset into the buffer--no bookkeeping, no
+/// fragmentation, since the hard work happened offline. `idealloc` never
+/// owns memory itself; the caller is expected to have allocated a buffer
+/// at least `best_opt` bytes wide and keep it alive for as long as the
+/// arena is in use.
+pub struct PlacedArena {
+    buffer_base:    *mut u8,
+    capacity:       ByteSteps,
+    // Scheduled jobs in ascending birth order--i.e., the order in which
+    // their `alloc` calls are expected to arrive at runtime.
+    schedule:       Vec<Rc<PlacedJob>>,
+    // Index into `schedule` of the next job to be served.
+    cursor:         Cell<usize>,
+    // Jobs currently "allocated", keyed by the address handed back to
+    // the caller, so `dealloc`/`realloc`/`usable_size` can find their
+    // way back to the scheduled slot.
+    live:           RefCell<HashMap<usize, Rc<PlacedJob>>>,
+}
+
+#[derive(Error, Debug)]
+pub enum ArenaError {
+    #[error("arena schedule exhausted--no more jobs were scheduled to be born")]
+    ScheduleExhausted,
+    #[error("layout ({requested} bytes, align {align}) does not fit the scheduled slot ({available} bytes)")]
+    LayoutMismatch {
+        requested:  ByteSteps,
+        align:      ByteSteps,
+        available:  ByteSteps,
+    },
+    #[error("pointer was not handed out by this arena")]
+    UnknownPointer,
+    #[error("growing in place would exceed the scheduled slot's boxed size")]
+    WouldRelocate,
+}
+
+impl PlacedArena {
+    /// Builds a replayable arena out of a finished placement.
+    ///
+    /// `buffer_base` must point to a buffer at least `best_opt` bytes
+    /// wide; `registry` is the [PlacedJobRegistry] of a completed
+    /// `idealloc` run.
+    pub fn new(
+        registry:       &PlacedJobRegistry,
+        buffer_base:    *mut u8,
+        best_opt:       ByteSteps,
+    ) -> Self {
+        let mut schedule: Vec<Rc<PlacedJob>> = registry.values().cloned().collect();
+        schedule.sort_unstable_by(|a, b| {
+            a.descr.birth.cmp(&b.descr.birth).then(a.descr.id.cmp(&b.descr.id))
+        });
+
+        Self {
+            buffer_base,
+            capacity:   best_opt,
+            schedule,
+            cursor:     Cell::new(0),
+            live:       RefCell::new(HashMap::new()),
+        }
+    }
+
+    #[inline(always)]
+    fn addr_of(&self, job: &PlacedJob) -> ByteSteps {
+        self.buffer_base as ByteSteps
+            + job.get_corrected_offset(self.buffer_base as ByteSteps, job.offset.get())
+    }
+
+    /// Serves `layout` out of the next scheduled slot, in birth order.
+    ///
+    /// Fails if the schedule is exhausted, or if the next slot's size
+    /// or address doesn't honor `layout`'s requirements--this should
+    /// never happen for a `layout` matching the job the schedule was
+    /// built from, and is the arena's own liveness invariant check.
+    pub fn alloc(&self, layout: Layout) -> Result<*mut u8, ArenaError> {
+        let idx = self.cursor.get();
+        let job = self.schedule.get(idx)
+            .ok_or(ArenaError::ScheduleExhausted)?
+            .clone();
+        self.cursor.set(idx + 1);
+
+        if layout.size() > job.descr.size {
+            return Err(ArenaError::LayoutMismatch {
+                requested:  layout.size(),
+                align:      layout.align(),
+                available:  job.descr.size,
+            });
+        }
+        let addr = self.addr_of(&job);
+        if addr % layout.align() != 0 {
+            return Err(ArenaError::LayoutMismatch {
+                requested:  layout.size(),
+                align:      layout.align(),
+                available:  job.descr.size,
+            });
+        }
+
+        let ptr = addr as *mut u8;
+        self.live.borrow_mut().insert(ptr as usize, job);
+        Ok(ptr)
+    }
+
+    /// Like [`alloc`](PlacedArena::alloc), but zeroes the served slot.
+    pub fn alloc_zeroed(&self, layout: Layout) -> Result<*mut u8, ArenaError> {
+        let ptr = self.alloc(layout)?;
+        unsafe { core::ptr::write_bytes(ptr, 0, layout.size()); }
+        Ok(ptr)
+    }
+
+    /// Retires `ptr`, freeing it up for the arena's own bookkeeping.
+    ///
+    /// The backing slot isn't reused--`idealloc`'s schedule already
+    /// accounts for the job's death when it placed later jobs--this
+    /// only stops `realloc`/`usable_size` from recognizing `ptr`.
+    pub fn dealloc(&self, ptr: *mut u8, _layout: Layout) -> Result<(), ArenaError> {
+        self.live.borrow_mut()
+            .remove(&(ptr as usize))
+            .map(|_| ())
+            .ok_or(ArenaError::UnknownPointer)
+    }
+
+    /// Grows or shrinks `ptr`'s allocation in place when the scheduled
+    /// slot still has room for `new_size`.
+    ///
+    /// Mirrors a `grow_in_place`-style contract: on failure, `ptr` is
+    /// left untouched and still valid--callers must fall back to an
+    /// `alloc` + copy + `dealloc` themselves, since this arena never
+    /// relocates a job out of its scheduled slot.
+    pub fn realloc(
+        &self,
+        ptr:        *mut u8,
+        old_layout: Layout,
+        new_size:   ByteSteps,
+    ) -> Result<*mut u8, ArenaError> {
+        let live = self.live.borrow();
+        let job = live.get(&(ptr as usize)).ok_or(ArenaError::UnknownPointer)?;
+        if new_size <= job.descr.size && (ptr as ByteSteps) % old_layout.align() == 0 {
+            Ok(ptr)
+        } else {
+            Err(ArenaError::WouldRelocate)
+        }
+    }
+
+    /// Returns the scheduled slot's real size for `ptr`, which may
+    /// exceed the `req_size` originally requested for it.
+    pub fn usable_size(&self, ptr: *mut u8) -> Result<ByteSteps, ArenaError> {
+        self.live.borrow()
+            .get(&(ptr as usize))
+            .map(|j| j.descr.size)
+            .ok_or(ArenaError::UnknownPointer)
+    }
+
+    /// The arena's total backing capacity, i.e., `best_opt`.
+    pub fn capacity(&self) -> ByteSteps {
+        self.capacity
+    }
+}
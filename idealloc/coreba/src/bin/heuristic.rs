@@ -1,4 +1,5 @@
 use std::usize;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use algo::placement::do_best_fit;
 use coreba::*;
@@ -39,6 +40,23 @@ struct Args {
     #[arg(short, long, default_value_t = 1)]
     #[arg(value_parser = clap::value_parser!(ByteSteps))]
     lives:  ByteSteps,
+
+    /// Spread random-ordering lives across a rayon thread pool,
+    /// instead of trying them out strictly one at a time.
+    #[arg(short, long, default_value_t = false)]
+    #[arg(value_parser = clap::value_parser!(bool))]
+    parallel: bool,
+
+    /// Frontier size for beam-search ordering.
+    #[arg(long, default_value_t = 1)]
+    #[arg(value_parser = clap::value_parser!(usize))]
+    beam_width: usize,
+
+    /// How many candidate next jobs each beam-search state
+    /// branches into, per expansion step.
+    #[arg(long, default_value_t = 1)]
+    #[arg(value_parser = clap::value_parser!(usize))]
+    branch: usize,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -51,6 +69,12 @@ enum JobOrdering {
     Area,
     /// A random permutation
     Random,
+    /// Searches the ordering space with a bounded-width beam,
+    /// squeezing one job at a time into each frontier state.
+    Beam,
+    /// Best-first branch-and-bound search, pruned by the
+    /// admissible partial-makespan lower bound.
+    BestFirst,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -83,64 +107,41 @@ fn main() {
         }
     }.unwrap(); 
     let load = get_load(&set);
-    let (ig, registry): (Option<InterferenceGraph>, PlacedJobRegistry) = if cli.graph {
-        let mut registry: PlacedJobRegistry = HashMap::new();
-        let mut events = get_events(&set);
-        let mut res: InterferenceGraph = HashMap::new();
-        let mut live: PlacedJobRegistry = HashMap::new();
-        while let Some(e) = events.pop() {
-            match e.evt_t {
-                EventKind::Birth    => {
-                    let init_vec: PlacedJobSet = live.values()
-                        .cloned()
-                        .collect();
-                    let new_entry = Rc::new(PlacedJob::new(e.job.clone()));
-                    // First, add a new entry, initialized to the currently live jobs.
-                    res.insert(e.job.id, init_vec);
-                    registry.insert(e.job.id, new_entry.clone());
-                    for (_, j) in &live {
-                        // Update currently live jobs' vectors with the new entry.
-                        let vec_handle = res.get_mut(&j.descr.id).unwrap();
-                        vec_handle.push(new_entry.clone());
-                    }
-                    // Add new entry to currently live jobs.
-                    live.insert(e.job.id, new_entry);
-                },
-                EventKind::Death    => {
-                    assert!(live.remove(&e.job.id).is_some());
-                },
-            }
-        }
-
-        (Some(res), registry)
-    } else { (None, set.iter()
-        .cloned()
-        .map(|j| (j.get_id(), Rc::new(PlacedJob::new(j))))
-        .collect::<PlacedJobRegistry>()) };
+    let (ig, registry) = build_ig_registry(&set, cli.graph);
     let total = Instant::now();
     let mut lives_left = cli.lives;
     let mut best_makespan = usize::MAX;
     let makespan = match cli.order {
         JobOrdering::Random => {
-            let mut shuffled_ids: Vec<u32> = registry.values().map(|pj| pj.descr.id).collect();
-            let mut rng = rand::thread_rng();
-            let mut iters = 0;
-            loop {
-                shuffled_ids.shuffle(&mut rng);
-                let ordered = shuffled_ids.iter().map(|id| registry.get(id).unwrap().clone()).collect();
-                let test_makespan = gen_placement(ordered, &ig, cli.fit, cli.start, best_makespan, iters);
-                if test_makespan == load { break test_makespan; }
-                if test_makespan < best_makespan {
-                    best_makespan = test_makespan;
-                }
-                lives_left -= 1;
-                if lives_left > 0 { 
-                    iters += 1;
-                    continue; 
+            if cli.parallel {
+                random_multistart_parallel(&set, cli.graph, cli.fit, cli.start, cli.lives, load)
+            } else {
+                let mut shuffled_ids: Vec<u32> = registry.values().map(|pj| pj.descr.id).collect();
+                let mut rng = rand::thread_rng();
+                let mut iters = 0;
+                loop {
+                    shuffled_ids.shuffle(&mut rng);
+                    let ordered = shuffled_ids.iter().map(|id| registry.get(id).unwrap().clone()).collect();
+                    let test_makespan = gen_placement(ordered, &ig, cli.fit, cli.start, best_makespan, iters);
+                    if test_makespan == load { break test_makespan; }
+                    if test_makespan < best_makespan {
+                        best_makespan = test_makespan;
+                    }
+                    lives_left -= 1;
+                    if lives_left > 0 {
+                        iters += 1;
+                        continue;
+                    }
+                    break best_makespan;
                 }
-                break best_makespan;
             }
         },
+        JobOrdering::Beam   => {
+            beam_search(&set, cli.start, load, cli.beam_width, cli.branch)
+        },
+        JobOrdering::BestFirst  => {
+            best_first_search(&set, cli.start, load, cli.lives)
+        },
         _   => {
             let ordered: PlacedJobSet = match cli.order {
                 JobOrdering::Birth  => {
@@ -161,7 +162,7 @@ fn main() {
                         .cloned()
                         .collect()
                 },
-                JobOrdering::Random => { panic!("Unreachable branch reached."); }
+                JobOrdering::Random | JobOrdering::Beam | JobOrdering::BestFirst => { panic!("Unreachable branch reached."); }
             };
             gen_placement(ordered, &ig, cli.fit, cli.start, usize::MAX, 0)
         },
@@ -178,6 +179,330 @@ fn main() {
     );
 }
 
+/// Builds an interference graph (if `with_graph`) and a fresh
+/// [PlacedJobRegistry] over `set`. Kept as a standalone helper
+/// because parallel multi-start needs a private, thread-local
+/// copy of both per worker (neither `Rc<PlacedJob>` nor `Cell`
+/// offsets are `Send`).
+fn build_ig_registry(
+    set:        &JobSet,
+    with_graph: bool,
+) -> (Option<InterferenceGraph>, PlacedJobRegistry) {
+    if with_graph {
+        let mut registry: PlacedJobRegistry = HashMap::new();
+        let mut events = get_events(set);
+        let mut res: InterferenceGraph = HashMap::new();
+        let mut live: PlacedJobRegistry = HashMap::new();
+        while let Some(e) = events.pop() {
+            match e.evt_t {
+                EventKind::Birth    => {
+                    let init_vec: PlacedJobSet = live.values()
+                        .cloned()
+                        .collect();
+                    let new_entry = Rc::new(PlacedJob::new(e.job.clone()));
+                    // First, add a new entry, initialized to the currently live jobs.
+                    res.insert(e.job.id, init_vec);
+                    registry.insert(e.job.id, new_entry.clone());
+                    for (_, j) in &live {
+                        // Update currently live jobs' vectors with the new entry.
+                        let vec_handle = res.get_mut(&j.descr.id).unwrap();
+                        vec_handle.push(new_entry.clone());
+                    }
+                    // Add new entry to currently live jobs.
+                    live.insert(e.job.id, new_entry);
+                },
+                EventKind::Death    => {
+                    assert!(live.remove(&e.job.id).is_some());
+                },
+            }
+        }
+
+        (Some(res), registry)
+    } else { (None, set.iter()
+        .cloned()
+        .map(|j| (j.get_id(), Rc::new(PlacedJob::new(j))))
+        .collect::<PlacedJobRegistry>()) }
+}
+
+/// Partitions `lives` random-ordering restarts across a rayon
+/// thread pool. Each worker builds its own [PlacedJobRegistry]
+/// (and interference graph, if requested) from `set` so that
+/// mutation of `Cell` offsets stays thread-local, and only the
+/// resulting makespans are reduced across workers.
+///
+/// The `lives` budget itself is shared through an [AtomicUsize]
+/// that every worker decrements before running a trial, so the
+/// parallel path spends exactly `lives` trials in total--same as
+/// the sequential search it mirrors--instead of each of the
+/// `num_workers` workers getting its own full share rounded up.
+///
+/// The running global best is shared through a second [AtomicUsize]
+/// so that every worker can feed it to `gen_placement` as an
+/// early-stopping `makesp_lim`, and so that all workers wind down
+/// as soon as any one of them reaches `load` (the optimum).
+fn random_multistart_parallel(
+    set:    &JobSet,
+    graph:  bool,
+    fit:    JobFit,
+    start:  ByteSteps,
+    lives:  ByteSteps,
+    load:   ByteSteps,
+) -> ByteSteps {
+    let num_workers = rayon::current_num_threads().max(1);
+    let lives_left = std::sync::Arc::new(AtomicUsize::new(lives));
+    let global_best = std::sync::Arc::new(AtomicUsize::new(usize::MAX));
+
+    (0..num_workers)
+        .into_par_iter()
+        .map(|_| {
+            let (ig, registry) = build_ig_registry(set, graph);
+            let mut shuffled_ids: Vec<u32> = registry.values().map(|pj| pj.descr.id).collect();
+            let mut rng = rand::thread_rng();
+            let mut local_best = usize::MAX;
+            let mut iters = 0u32;
+            loop {
+                if global_best.load(Ordering::Relaxed) == load { break; }
+                if lives_left.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1)).is_err() {
+                    break;
+                }
+                shuffled_ids.shuffle(&mut rng);
+                let ordered = shuffled_ids.iter().map(|id| registry.get(id).unwrap().clone()).collect();
+                let lim = global_best.load(Ordering::Relaxed).min(local_best);
+                let test_makespan = gen_placement(ordered, &ig, fit, start, lim, iters);
+                iters += 1;
+                if test_makespan < local_best {
+                    local_best = test_makespan;
+                    let mut cur = global_best.load(Ordering::Relaxed);
+                    while test_makespan < cur {
+                        match global_best.compare_exchange_weak(cur, test_makespan, Ordering::Relaxed, Ordering::Relaxed) {
+                            Ok(_)   => break,
+                            Err(v)  => cur = v,
+                        }
+                    }
+                    if local_best == load { break; }
+                }
+            }
+            local_best
+        })
+        .min()
+        .unwrap_or(usize::MAX)
+}
+
+/// A partial placement explored by [beam_search]: the jobs already
+/// squeezed into it, the jobs not yet placed, and the
+/// resulting partial makespan.
+#[derive(Clone)]
+struct BeamState {
+    squeezed:   PlacedJobSet,
+    remaining:  Vec<Arc<Job>>,
+    makespan:   ByteSteps,
+}
+
+/// Searches the space of job orderings with a frontier of at most
+/// `width` states, instead of committing to a single sort or blind
+/// random restarts.
+///
+/// At each step every frontier state picks its `branch` most
+/// promising remaining jobs (largest area first), squeezes each one
+/// into its own existing placement, and the resulting children are
+/// scored by their partial makespan. Only the `width` lowest-scoring
+/// children survive into the next step (ties broken by the largest
+/// remaining total area). Search stops early the moment a complete
+/// state's makespan equals `load`, since no placement can do better;
+/// a partial makespan equal to `load` does not end the search.
+fn beam_search(
+    set:        &JobSet,
+    start_addr: ByteSteps,
+    load:       ByteSteps,
+    width:      usize,
+    branch:     usize,
+) -> ByteSteps {
+    let mut frontier = vec![BeamState {
+        squeezed:   vec![],
+        remaining:  set.clone(),
+        makespan:   0,
+    }];
+
+    while frontier.iter().any(|s| !s.remaining.is_empty()) {
+        let mut children: Vec<BeamState> = vec![];
+        for state in frontier {
+            if state.remaining.is_empty() {
+                children.push(state);
+                continue;
+            }
+            let top_k: Vec<Arc<Job>> = state.remaining
+                .iter()
+                .cloned()
+                .sorted_by(|a, b| b.area().cmp(&a.area()))
+                .take(branch)
+                .collect();
+            for next_job in top_k {
+                let mut squeezed = state.squeezed.clone();
+                let placed = squeeze_one(&squeezed, next_job.clone(), start_addr);
+                let makespan = state.makespan.max(placed.next_avail_offset());
+                squeezed.push(placed);
+                let remaining = state.remaining
+                    .iter()
+                    .filter(|j| j.id != next_job.id)
+                    .cloned()
+                    .collect();
+                children.push(BeamState { squeezed, remaining, makespan });
+            }
+        }
+
+        if let Some(done) = children.iter().find(|s| s.remaining.is_empty() && s.makespan == load) {
+            return done.makespan;
+        }
+
+        frontier = children.into_iter()
+            .sorted_by(|a, b| {
+                a.makespan.cmp(&b.makespan)
+                    .then_with(|| {
+                        let b_area: ByteSteps = b.remaining.iter().map(|j| j.area()).sum();
+                        let a_area: ByteSteps = a.remaining.iter().map(|j| j.area()).sum();
+                        b_area.cmp(&a_area)
+                    })
+            })
+            .take(width.max(1))
+            .collect();
+    }
+
+    frontier.into_iter()
+        .map(|s| s.makespan)
+        .min()
+        .unwrap_or(0)
+}
+
+/// A node in [best_first_search]'s search tree: the jobs already
+/// squeezed, the jobs not yet placed, and `g`, the partial
+/// makespan achieved so far. Because `do_best_fit` places jobs in
+/// symbolic-offset order and the running makespan never decreases
+/// as jobs are added, `g` is an admissible lower bound on any
+/// completion of this state.
+struct BBState {
+    squeezed:   PlacedJobSet,
+    remaining:  Vec<Arc<Job>>,
+    g:          ByteSteps,
+}
+
+// `BinaryHeap` is a max-heap; we want the lowest-`g` state
+// popped first, so the ordering is reversed.
+impl Ord for BBState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.g.cmp(&self.g)
+    }
+}
+impl PartialOrd for BBState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for BBState {
+    fn eq(&self, other: &Self) -> bool {
+        self.g == other.g
+    }
+}
+impl Eq for BBState {}
+
+/// Best-first branch-and-bound search over job orderings. Maintains
+/// a min-heap of partial states keyed by `g`; each pop expands the
+/// lowest-`g` state by appending every remaining job (squeezing only
+/// the new job into the state's existing placement), pruning any
+/// child whose `g` already meets or exceeds the best complete
+/// makespan found so far. The first complete state popped whose `g`
+/// equals `load` is optimal and ends the search immediately.
+///
+/// Since the state space is factorial, total node expansions are
+/// capped at `node_budget` (the CLI's `--lives` budget); once
+/// exhausted, the best complete placement found so far is returned.
+fn best_first_search(
+    set:            &JobSet,
+    start_addr:     ByteSteps,
+    load:           ByteSteps,
+    node_budget:    ByteSteps,
+) -> ByteSteps {
+    let mut heap = BinaryHeap::new();
+    heap.push(BBState {
+        squeezed:   vec![],
+        remaining:  set.clone(),
+        g:          0,
+    });
+    let mut best_complete = ByteSteps::MAX;
+    let mut expansions = 0;
+
+    while let Some(state) = heap.pop() {
+        if state.g >= best_complete { continue; }
+        if state.remaining.is_empty() {
+            best_complete = state.g;
+            if best_complete == load { return best_complete; }
+            continue;
+        }
+        if expansions >= node_budget { break; }
+        expansions += 1;
+
+        for next_job in &state.remaining {
+            let mut squeezed = state.squeezed.clone();
+            let placed = squeeze_one(&squeezed, next_job.clone(), start_addr);
+            let g = state.g.max(placed.next_avail_offset());
+            if g >= best_complete { continue; }
+            squeezed.push(placed);
+            let remaining = state.remaining.iter()
+                .filter(|j| j.id != next_job.id)
+                .cloned()
+                .collect();
+            heap.push(BBState { squeezed, remaining, g });
+        }
+    }
+
+    best_complete
+}
+
+/// Squeezes a single new job into an already-squeezed set, using
+/// the same ascending-offset gap scan as [do_best_fit]/[do_naive_fit],
+/// without re-placing the rest of the prefix.
+fn squeeze_one(
+    squeezed:   &PlacedJobSet,
+    job:        Arc<Job>,
+    start_addr: ByteSteps,
+) -> Rc<PlacedJob> {
+    let to_squeeze = Rc::new(PlacedJob::new(job));
+    let min_gap_size = to_squeeze.descr.size;
+    let mut offset_runner = 0;
+    let mut smallest_gap = ByteSteps::MAX;
+    let mut best_offset: Option<ByteSteps> = None;
+    let mut jobs_vec = squeezed.iter()
+        .filter(|j| j.overlaps_with(&to_squeeze))
+        .sorted_unstable()
+        .rev()
+        .peekable();
+
+    while let Some(next_job) = jobs_vec.peek() {
+        let njo = next_job.offset.get();
+        if njo > offset_runner {
+            let test_off = to_squeeze.get_corrected_offset(start_addr, offset_runner);
+            if njo > test_off && njo - test_off >= min_gap_size {
+                let gap = njo - test_off;
+                if gap < smallest_gap {
+                    smallest_gap = gap;
+                    best_offset = Some(test_off);
+                }
+            }
+            offset_runner = test_off.max(next_job.next_avail_offset());
+        } else {
+            offset_runner = offset_runner.max(next_job.next_avail_offset());
+        }
+        jobs_vec.next();
+    }
+
+    if let Some(o) = best_offset {
+        to_squeeze.offset.set(o);
+    } else {
+        to_squeeze.offset.set(offset_runner);
+    }
+
+    to_squeeze
+}
+
 fn gen_placement(
     ordered:    PlacedJobSet,
     ig:         &Option<InterferenceGraph>,
@@ -216,7 +541,10 @@ fn do_naive_fit(
     start_addr: ByteSteps
 ) -> ByteSteps {
     let mut max_address = 0;
-    let mut squeezed: PlacedJobSet = vec![];
+    // Spatial index of already-squeezed jobs: turns the per-job
+    // candidate search from a full scan of every placed job into
+    // a vertical-strip (time-range) query.
+    let mut index: PlacedJobIndex = PlacedJobIndex::new();
     // Traverse loosely placed jobs in ascending offset.
     while let Some(to_squeeze) = loose.pop() {
         let min_gap_size = to_squeeze.descr.size;
@@ -227,7 +555,7 @@ fn do_naive_fit(
         // the current one in ascending offset. You're looking
         // for the smallest gap which fits the job, alignment
         // requirements included.
-        let mut jobs_vec = squeezed.iter()
+        let mut jobs_vec = query_time_overlap(&index, &to_squeeze)
             .filter(|j| { j.overlaps_with(&to_squeeze) })
             .sorted_unstable()
             .rev()
@@ -263,7 +591,7 @@ fn do_naive_fit(
         if cand_makespan > max_address {
             max_address = cand_makespan;
         }
-        squeezed.push(to_squeeze);
+        index.insert(PlacedBox { job: to_squeeze });
     };
 
     max_address
@@ -25,7 +25,18 @@ struct Args {
     /// Maximum number of tries allowed to beat bootstrap heuristic
     #[arg(short = 'l', long, default_value_t = 1)]
     #[arg(value_parser = clap::value_parser!(u32))]
-    max_lives:  u32
+    max_lives:  u32,
+
+    /// How many of those tries to run concurrently, as one Rayon-backed
+    /// wave, before checking whether to keep going
+    #[arg(short = 'b', long, default_value_t = 1)]
+    #[arg(value_parser = clap::value_parser!(u32))]
+    batch_width: u32,
+
+    /// Write per-iteration convergence telemetry (CSV) to this path
+    #[cfg(all(feature = "telemetry", feature = "std"))]
+    #[arg(long, value_parser = clap::value_parser!(PathBuf))]
+    telemetry:  Option<PathBuf>,
 }
 
 fn main() {
@@ -48,5 +59,18 @@ fn main() {
         },
         InpuType::TRC   => { panic!("TRC files must first be fed to the `adapt` binary!"); },
     }.unwrap();
-    coreba::algo::idealloc(set, cli.max_frag, cli.start, cli.max_lives);
+    #[cfg(all(feature = "telemetry", feature = "std"))]
+    let telemetry = cli.telemetry.map(|p| {
+        let file = std::fs::File::create(p).expect("Couldn't create telemetry file");
+        coreba::telemetry::TelemetrySink::new(Box::new(file))
+    });
+    coreba::algo::idealloc(
+        set,
+        cli.max_frag,
+        cli.start,
+        cli.max_lives,
+        cli.batch_width,
+        #[cfg(all(feature = "telemetry", feature = "std"))]
+        telemetry,
+    );
 }
\ No newline at end of file
@@ -1,20 +1,57 @@
+// Everything below `std` actually needs--filesystem access, wall-clock
+// timing, thread pools--is gated behind the `std` feature (on by
+// default). The placement core itself (this module's types, `job`,
+// `instance`, `jobset`) only needs heap allocation and draws on
+// `alloc`/`core` alone when `std` is off.
+#[cfg(feature = "std")]
 pub use std::{
-    rc::Rc,
-    sync::{Arc, Mutex},
-    io::{BufRead, BufReader, Read},
-    collections::{HashMap, BinaryHeap, BTreeSet, HashSet},
+    io::{BufRead, BufReader, Read, Write},
     path::PathBuf,
-    iter::Peekable,
-    hash::Hash,
     backtrace::Backtrace,
-    cell::Cell,
     time::Instant,
 };
+#[cfg(feature = "std")]
+pub use rayon::prelude::*;
+
+#[cfg(feature = "std")]
+pub use std::{
+    rc::Rc,
+    sync::Arc,
+    collections::{BinaryHeap, BTreeSet},
+    cell::{Cell, RefCell},
+    iter::Peekable,
+    hash::Hash,
+};
+#[cfg(not(feature = "std"))]
+pub use alloc::{
+    rc::Rc,
+    sync::Arc,
+    collections::{BinaryHeap, BTreeSet},
+    vec::Vec,
+    boxed::Box,
+    string::String,
+    vec,
+};
+#[cfg(not(feature = "std"))]
+pub use core::{
+    cell::{Cell, RefCell},
+    iter::Peekable,
+    hash::Hash,
+};
+
+// `HashMap`/`HashSet` back the interference graph and the placed-job
+// registry--both part of the core placement engine--so they need a
+// `no_std`-friendly implementation too.
+#[cfg(feature = "std")]
+pub use std::collections::{HashMap, HashSet};
+#[cfg(not(feature = "std"))]
+pub use hashbrown::{HashMap, HashSet};
 pub use thiserror::Error;
 pub use itertools::Itertools;
-pub use rayon::prelude::*;
 pub use indexmap::IndexMap;
+#[cfg(feature = "std")]
 pub use clap::{Parser, ValueEnum};
+use rstar::{RTree, RTreeObject, AABB};
 
 pub use crate::{Instance, Job,
     jobset::*,
@@ -44,6 +81,7 @@ pub type JobSet = Vec<Arc<Job>>;
 /// type that reads from a Linux-born `.trc` binary file.
 ///
 /// The user can implement their own types as needed.
+#[cfg(feature = "std")]
 pub trait JobGen<T> {
     fn new(path: PathBuf) -> Self;
     /// Either a set of jobs is successfully returned, or some
@@ -69,13 +107,18 @@ pub struct JobError {
 //
 // To write your own interface, simply make sure that it
 // satisfies the `JobGen` trait.
-
+//
+// Every type here touches the filesystem, so the whole block is
+// gated behind `std`--a `no_std` target is expected to build its
+// own `JobSet` in memory and feed it straight to the placement core.
+#[cfg(feature = "std")]
 pub struct PLCParser {
     pub path: PathBuf,
 }
 
 pub const PLC_FIELDS_NUM: usize = 8;
 
+#[cfg(feature = "std")]
 impl JobGen<&[u8; 8 * PLC_FIELDS_NUM]> for PLCParser {
     fn new(path: PathBuf) -> Self {
         Self {
@@ -126,12 +169,34 @@ impl JobGen<&[u8; 8 * PLC_FIELDS_NUM]> for PLCParser {
     }
 }
 
+#[cfg(feature = "std")]
+impl PLCParser {
+    /// Lazily yields jobs frame by frame as the file is read, instead
+    /// of eagerly materializing the whole trace like
+    /// [`read_jobs`](JobGen::read_jobs)--suitable for multi-gigabyte
+    /// `.trc` dumps.
+    pub fn stream_jobs(&self, _shift: ByteSteps) -> Result<impl Iterator<Item = Result<Job, Box<dyn std::error::Error>>> + '_, Box<dyn std::error::Error>> {
+        let fd = std::fs::File::open(self.path.as_path())?;
+        let mut reader = BufReader::new(fd);
+        Ok(core::iter::from_fn(move || {
+            let mut buffer: [u8; 8 * PLC_FIELDS_NUM] = [0; 8 * PLC_FIELDS_NUM];
+            match reader.read_exact(&mut buffer) {
+                Ok(())  => Some(Ok(self.gen_single(&buffer, 62))),
+                Err(e)  if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
+                Err(e)  => Some(Err(Box::new(e) as Box<dyn std::error::Error>)),
+            }
+        }))
+    }
+}
+
 /// We adopt [`minimalloc`'s CSV](https://github.com/google/minimalloc)
 /// as the most standard format.
+#[cfg(feature = "std")]
 pub struct MinimalloCSVParser {
     pub path: PathBuf,
 }
 
+#[cfg(feature = "std")]
 impl JobGen<&[ByteSteps; 3]> for MinimalloCSVParser {
     fn new(path: PathBuf) -> Self {
         Self {
@@ -184,7 +249,42 @@ impl JobGen<&[ByteSteps; 3]> for MinimalloCSVParser {
             contents:           None,
             originals_boxed:    0,
             id
-        }        
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl MinimalloCSVParser {
+    /// Lazily yields jobs line by line as the file is read, instead of
+    /// eagerly materializing the whole CSV like
+    /// [`read_jobs`](JobGen::read_jobs)--suitable for multi-gigabyte
+    /// traces.
+    pub fn stream_jobs(&self, _shift: ByteSteps) -> Result<impl Iterator<Item = Result<Job, Box<dyn std::error::Error>>> + '_, Box<dyn std::error::Error>> {
+        let fd = std::fs::File::open(self.path.as_path())?;
+        let mut lines = BufReader::new(fd).lines()
+            // First line is the header!
+            .skip(1);
+        let mut next_id = 0;
+        Ok(core::iter::from_fn(move || {
+            let line = match lines.next()? {
+                Ok(l)   => l,
+                Err(e)  => return Some(Err(Box::new(e) as Box<dyn std::error::Error>)),
+            };
+            let mut data_buf: [ByteSteps; 3] = [0; 3];
+            for (idx, data) in line.split(',')
+                // First column is the id!
+                .skip(1)
+                .take(3)
+                .map(|x| {
+                    if let Ok(v) = usize::from_str_radix(x, 10) { v }
+                    else { panic!("Error while parsing CSV."); }
+                }).enumerate() {
+                    data_buf[idx] = data;
+            }
+            let job = self.gen_single(&data_buf, next_id);
+            next_id += 1;
+            Some(Ok(job))
+        }))
     }
 }
 
@@ -194,10 +294,12 @@ impl JobGen<&[ByteSteps; 3]> for MinimalloCSVParser {
 /// We introduce this additional type because IREE adopts
 /// *inclusive* semantics on both ends of a buffer's lifetime.
 /// Thus conversion is needed.
+#[cfg(feature = "std")]
 pub struct IREECSVParser {
     pub dirty:  PathBuf,
 }
 
+#[cfg(feature = "std")]
 impl JobGen<Job> for IREECSVParser {
     fn new(dirty: PathBuf) -> Self {
         Self {
@@ -257,6 +359,21 @@ impl JobGen<Job> for IREECSVParser {
         d
     }
 }
+
+#[cfg(feature = "std")]
+impl IREECSVParser {
+    /// Unlike [`PLCParser`]/[`MinimalloCSVParser`], this parser's
+    /// inclusive-to-exclusive lifetime conversion needs a full
+    /// event-sorted pass over the file (see `read_jobs` above), so
+    /// there's no meaningful way to stream it lazily. This falls back
+    /// to buffered mode and streams the *already materialized* result--
+    /// callers chasing large-trace behavior should reach for
+    /// `PLCParser`/`MinimalloCSVParser` directly instead.
+    pub fn stream_jobs(&self, shift: ByteSteps) -> Result<impl Iterator<Item = Result<Job, Box<dyn std::error::Error>>>, Box<dyn std::error::Error>> {
+        let jobs = self.read_jobs(shift)?;
+        Ok(jobs.into_iter().map(Ok))
+    }
+}
 //---END EXTERNAL INTERFACES
 
 //---START PLACEMENT PRIMITIVES
@@ -318,13 +435,13 @@ impl PlacedJob {
 // The INTERMEDIATE result of unboxing, that is, a first
 // loose placement, will be a min-heap on the jobs' offsets.
 impl Ord for PlacedJob {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         other.offset.cmp(&self.offset)
     }
 }
 
 impl PartialOrd for PlacedJob {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -338,13 +455,56 @@ impl PartialEq for PlacedJob {
 impl Eq for PlacedJob {}
 
 impl Hash for PlacedJob {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         // A `PlacedJob` is hashed according to the
         // underlying `Job` ID.
         self.descr.hash(state);
     }
 }
 
+/// A spatial wrapper around a [PlacedJob], letting it live inside
+/// an [rstar::RTree] keyed on the 2D box `(time interval) x
+/// (placed address interval)`.
+///
+/// `do_best_fit`/`do_naive_fit` use this to find only the already-
+/// squeezed jobs whose lifetime overlaps a candidate's, via a
+/// vertical-strip query, instead of scanning every squeezed job.
+pub struct PlacedBox {
+    pub job: Rc<PlacedJob>,
+}
+
+impl RTreeObject for PlacedBox {
+    type Envelope = AABB<[i64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        let birth = self.job.descr.birth as i64;
+        let death = self.job.descr.death as i64;
+        let offset = self.job.offset.get() as i64;
+        let top = offset + self.job.descr.size as i64;
+        AABB::from_corners([birth, offset], [death, top])
+    }
+}
+
+/// An index of already-squeezed jobs, queried by time range to
+/// accelerate gap-finding during placement.
+pub type PlacedJobIndex = RTree<PlacedBox>;
+
+/// Returns every indexed job whose time range intersects
+/// `to_place`'s. The query is a coarse, closed-interval
+/// vertical-strip test--callers still need [`PlacedJob::overlaps_with`]
+/// (or an equivalent membership check) for the exact open-interval
+/// semantics.
+pub fn query_time_overlap<'a>(
+    index:      &'a PlacedJobIndex,
+    to_place:   &PlacedJob,
+) -> impl Iterator<Item = Rc<PlacedJob>> + 'a {
+    let lo = to_place.descr.birth as i64;
+    let hi = to_place.descr.death as i64;
+    let envelope = AABB::from_corners([lo, i64::MIN], [hi, i64::MAX]);
+    index.locate_in_envelope_intersecting(&envelope)
+        .map(|b| b.job.clone())
+}
+
 // No `Arc` needed here, since we shall
 // work single-threadedly.
 pub type PlacedJobSet = Vec<Rc<PlacedJob>>;
@@ -406,14 +566,12 @@ impl T2Control {
     /// at least one piece in `jobs` is live.
     #[inline(always)]
     pub fn gen_crit(
-        jobs:   &Instance, 
-        left:   ByteSteps, 
+        jobs:   &Instance,
+        left:   ByteSteps,
         right:  ByteSteps
     ) -> ByteSteps {
         // What follows is the simplest, most naive, but also
         // most safe implementation of `gen_crit`.
-        use rand::{Rng, thread_rng};
-
         debug_assert!(left + 1 < right, "Bad range found.");
         let mut pts: Vec<ByteSteps> = vec![];
         let mut evts = get_events(&jobs.jobs);
@@ -434,10 +592,74 @@ impl T2Control {
         };
 
         // Rust ranges (x..y) are low-inclusive, upper-exclusive.
-        pts[thread_rng().gen_range(0..pts.len())]
+        pts[random_index(pts.len())]
     }
 }
 
+/// Pins down [`random_index`]'s draws on `std` targets, for callers that
+/// want a seeded run (e.g. `toy_cxx`'s C++ bridge, whose
+/// `PlacementRequest::seed` this backs) instead of OS entropy.
+///
+/// `Some(seed)` switches every subsequent `random_index` call, on any
+/// thread, over to a `SmallRng` reseeded from a counter that starts at
+/// `seed` and advances the same way the `no_std` fallback below already
+/// does, so the sequence of drawn values is fixed by `seed`. Which
+/// [`T2Control::gen_crit`] call gets which value is not: boxing makes
+/// those calls from Rayon workers (`c_15`'s buckets, `t_2`'s X_i
+/// recursion, `idealloc`'s parallel waves) in no fixed order, so two
+/// runs with the same `seed` can still produce different placements.
+/// `None` reverts to OS entropy.
+#[cfg(feature = "std")]
+pub fn seed_rng(seed: Option<u64>) {
+    use core::sync::atomic::Ordering;
+    match seed {
+        Some(s) => {
+            SEED_COUNTER.store(s, Ordering::Relaxed);
+            SEEDED.store(true, Ordering::Relaxed);
+        },
+        None => SEEDED.store(false, Ordering::Relaxed),
+    }
+}
+
+#[cfg(feature = "std")]
+static SEEDED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+#[cfg(feature = "std")]
+static SEED_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Picks a uniformly random index in `0..n`.
+///
+/// On `std` targets this draws from the OS entropy source via
+/// [`rand::thread_rng`], unless [`seed_rng`] has pinned it down. On
+/// `no_std` targets there is no entropy source at all, so we always
+/// fall back to a `SmallRng` reseeded from a monotonically advancing
+/// counter--good enough to diversify [`T2Control::gen_crit`]'s choice
+/// of critical point across calls, without threading an RNG handle
+/// through the recursive/parallel boxing call chain.
+#[cfg(feature = "std")]
+#[inline(always)]
+fn random_index(n: usize) -> usize {
+    use rand::{Rng, SeedableRng, thread_rng, rngs::SmallRng};
+    use core::sync::atomic::Ordering;
+
+    if SEEDED.load(Ordering::Relaxed) {
+        let s = SEED_COUNTER.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed);
+        SmallRng::seed_from_u64(s).gen_range(0..n)
+    } else {
+        thread_rng().gen_range(0..n)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+fn random_index(n: usize) -> usize {
+    use rand::{Rng, SeedableRng, rngs::SmallRng};
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    static SEED: AtomicU64 = AtomicU64::new(0x2545_F491_4F6C_DD1D);
+    let s = SEED.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed);
+    SmallRng::seed_from_u64(s).gen_range(0..n)
+}
+
 #[derive(PartialEq, Eq, Clone)]
 /// An [Event] is either a birth or a death.
 pub enum EventKind {
@@ -464,25 +686,25 @@ pub struct Event {
 pub type Events = BinaryHeap<Event>;
 
 impl Ord for Event {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         // We're using a BinaryHeap, which is
         // a max-priority queue. We want a min-one
         // and so we're reversing the order of `cmp`.
         other.time.cmp(&self.time)
             .then(
                 if self.evt_t == other.evt_t {
-                    std::cmp::Ordering::Equal
+                    core::cmp::Ordering::Equal
                 } else {
                     match self.evt_t {
                         // Prioritize deaths over births.
-                        EventKind::Birth    => { std::cmp::Ordering::Less },
-                        EventKind::Death    => { std::cmp::Ordering::Greater },
+                        EventKind::Birth    => { core::cmp::Ordering::Less },
+                        EventKind::Death    => { core::cmp::Ordering::Greater },
                     }
                 })
     }
 }
 impl PartialOrd for Event {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -511,7 +733,7 @@ impl New for VertStripJob {
 }
 
 impl Ord for VertStripJob {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.job
             .death
             .cmp(&other.job.death)
@@ -519,7 +741,7 @@ impl Ord for VertStripJob {
 }
 
 impl PartialOrd for VertStripJob {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -554,14 +776,14 @@ impl New for HorStripJob {
 }
 
 impl Ord for HorStripJob {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         other.job
             .cmp(&self.job)
     }
 }
 
 impl PartialOrd for HorStripJob {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -639,6 +861,9 @@ pub fn strip_cuttin<T>(
     res
 }
 
+// Only the `std`-only CLI binaries (`idealloc`, `heuristic`) ever
+// construct one of these, via `clap`'s derive.
+#[cfg(feature = "std")]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
 pub enum InpuType {
     /// A CSV file using the minimalloc benchmarks format (exclusive endpoints)
@@ -653,7 +878,8 @@ pub enum InpuType {
     TRC,
 }
 
-pub fn read_from_path<T, B>(file_path: PathBuf, shift: ByteSteps) -> Result<JobSet, Box<dyn std::error::Error>> 
+#[cfg(feature = "std")]
+pub fn read_from_path<T, B>(file_path: PathBuf, shift: ByteSteps) -> Result<JobSet, Box<dyn std::error::Error>>
 where T: JobGen<B> {
     let parser = T::new(file_path);
     let jobs = parser.read_jobs(shift)?;
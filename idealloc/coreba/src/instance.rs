@@ -50,24 +50,34 @@ impl Instance {
     /// Splits instance to unit-height buckets, in the
     /// context of Corollary 15. Each bucket is indexed
     /// by the height to be given to Theorem 2.
+    ///
+    /// A job of `size` `s` belongs to the unique bucket `i` with
+    /// `(1 + ε)^(i-1) < s <= (1 + ε)^i` (`i = 0` for `s == 1`, since
+    /// there is no `i = -1` bucket below it)--so `i` is computed
+    /// directly as `ceil(ln(s) / ln(1 + ε))`, and every job is placed
+    /// in a single linear pass instead of re-scanning the shrinking
+    /// remainder once per candidate height.
     #[inline(always)]
-    pub fn make_buckets(mut source: Rc<Self>, epsilon: f64) -> HashMap<ByteSteps, Instance> {
-        let mut res = HashMap::new();
-        let mut prev_floor = 1.0 / (1.0 + epsilon);
-        let mut i = 0;
-        while source.jobs.len() > 0 {
-            let h = (1.0 + epsilon).powi(i);
-            if source.jobs.iter().any(|j| j.size as f64 > prev_floor && j.size as f64 <= h) {
-                let h_split = h.floor() as ByteSteps;
-                let (toward_bucket, rem) = source.split_by_height(h_split);
-                res.insert(h_split, toward_bucket);
-                source = Rc::new(rem);
-            }
-            prev_floor = h;
-            i += 1;
+    pub fn make_buckets(source: Rc<Self>, epsilon: f64) -> HashMap<ByteSteps, Instance> {
+        let ln_base = (1.0 + epsilon).ln();
+        let mut by_index: HashMap<i32, JobSet> = HashMap::new();
+        for j in &source.jobs {
+            let i = if j.size <= 1 {
+                0
+            } else {
+                ((j.size as f64).ln() / ln_base).ceil() as i32
+            };
+            by_index.entry(i)
+                .and_modify(|v| v.push(j.clone()))
+                .or_insert_with(|| vec![j.clone()]);
         }
 
-        res
+        by_index.into_iter()
+            .map(|(i, jobs)| {
+                let h_split = (1.0 + epsilon).powi(i).floor() as ByteSteps;
+                (h_split, Instance::new(jobs))
+            })
+            .collect()
     }
 
     pub fn check_boxed_originals(&self, target: u32) -> bool {
@@ -243,7 +253,8 @@ impl Instance {
     }
 
     /// Does the same as [`Instance::merge_with`], but without consuming
-    /// `self`. Used in the context of consolidating `Mutex`-protected results.
+    /// `self`. Used as the pairwise combinator of a parallel tree
+    /// reduction, folding one worker's partial `Instance` into another's.
     #[inline(always)]
     pub fn merge_via_ref(&mut self, mut other: Self) {
         let to_join = self.jobs.len() + other.jobs.len();
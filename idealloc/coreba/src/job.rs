@@ -38,7 +38,9 @@ impl Job {
         contents:   JobSet,
         height:     ByteSteps,
     ) -> Self {
-        use std::{sync::atomic::AtomicU32, u32};
+        // `core`'s atomics work identically with or without an OS,
+        // so boxing can assign IDs on a `no_std` target too.
+        use core::sync::atomic::AtomicU32;
         static NEXT_ID: AtomicU32 = AtomicU32::new(u32::MAX);
 
         // The box must be high enough to enclose all jobs.
@@ -58,7 +60,7 @@ impl Job {
                 originals_boxed += 1;
             } else { originals_boxed += j.originals_boxed; }
         }
-        let id = NEXT_ID.fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+        let id = NEXT_ID.fetch_sub(1, core::sync::atomic::Ordering::Relaxed);
         assert!(id != u32::MAX / 2 + 1);
         Self {
             size:               height,
@@ -138,13 +140,13 @@ impl Job {
    of Job according to the `birth` field.
 */
 impl Ord for Job {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.birth.cmp(&other.birth)
     }
 }
 
 impl PartialOrd for Job {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -158,7 +160,7 @@ impl PartialEq for Job {
 impl Eq for Job {}
 
 impl Hash for Job {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.id.hash(state);
     }
 }
@@ -1,47 +1,42 @@
 use crate::helpe::*;
 
-/// Initializes a JobSet with a given set of jobs.
-/// A successfully returned JobSet is guaranteed to be
-/// compliant with all of `idealloc`'s assumptions. These are:
+/// Checks `j` against all of `idealloc`'s input assumptions. These are:
 /// - no job has zero size
 /// - all deaths are bigger than all births
 /// - no job has bad alignment (zero, or alloc. size not multiple of i)
 /// - all jobs are original
 /// - allocated job size is equal or greater to the requested one
 ///
+/// Returns the offending assumption's description, or `None` if `j`
+/// satisfies all of them. Shared between [`init`] and [`from_stream`].
+fn validity_error(j: &Job) -> Option<&'static str> {
+    if j.size == 0 {
+        Some("Job with 0 size found!")
+    } else if j.birth >= j.death {
+        Some("Job with birth >= death found!")
+    } else if j.alignment == Some(0) {
+        Some("Job with 0 alignment found!")
+    } else if !j.is_original() {
+        Some("Unoriginal job found! (non-empty contents)")
+    } else if j.originals_boxed != 0 {
+        Some("Unoriginal job found! (non-zero originals_boxed)")
+    } else if j.size < j.req_size {
+        Some("Job with req > alloc size found!")
+    } else {
+        None
+    }
+}
+
+/// Initializes a JobSet with a given set of jobs.
+/// A successfully returned JobSet is guaranteed to be
+/// compliant with all of `idealloc`'s assumptions (see [`validity_error`]).
+///
 /// This function is the gatekeeper to the rest of the library.
 pub fn init(mut in_elts: Vec<Job>) -> Result<JobSet, JobError> {
-    for (idx, j) in in_elts.iter_mut().enumerate() {
-        if j.size == 0 {
-            return Err(JobError {
-                message: String::from("Job with 0 size found!"),
-                culprit: in_elts.remove(idx),
-            });
-        } else if j.birth >= j.death {
-            return Err(JobError {
-                message: String::from("Job with birth >= death found!"),
-                culprit: in_elts.remove(idx),
-            });
-        } else if let Some(a) = j.alignment {
-            if a == 0 {
-                return Err(JobError {
-                    message: String::from("Job with 0 alignment found!"),
-                    culprit: in_elts.remove(idx),
-                });
-            }
-        } else if !j.is_original() {
-            return Err(JobError {
-                message: String::from("Unoriginal job found! (non-empty contents)"),
-                culprit: in_elts.remove(idx),
-            });
-        } else if j.originals_boxed != 0 {
-            return Err(JobError {
-                message: String::from("Unoriginal job found! (non-zero originals_boxed)"),
-                culprit: in_elts.remove(idx),
-            });
-        } else if j.size < j.req_size {
+    for idx in 0..in_elts.len() {
+        if let Some(message) = validity_error(&in_elts[idx]) {
             return Err(JobError {
-                message: String::from("Job with req > alloc size found!"),
+                message: String::from(message),
                 culprit: in_elts.remove(idx),
             });
         }
@@ -53,6 +48,41 @@ pub fn init(mut in_elts: Vec<Job>) -> Result<JobSet, JobError> {
         .collect())
 }
 
+/// Like [`init`], but consumes jobs as they arrive from `stream` instead
+/// of requiring the full set pre-materialized in a [Vec].
+///
+/// Meant to be fed directly from a streaming [crate::helpe::JobGen]
+/// reader (e.g. `PLCParser::stream_jobs`/`MinimalloCSVParser::stream_jobs`),
+/// so multi-gigabyte traces never need to sit fully in RAM just to pass
+/// validation. Computes the horizon `(min birth, max death)` online, as
+/// jobs stream past, so callers don't need a second full pass to get it.
+pub fn from_stream<I, E>(stream: I) -> Result<(JobSet, (ByteSteps, ByteSteps)), JobError>
+where
+    I: Iterator<Item = Result<Job, E>>,
+{
+    let mut res: JobSet = vec![];
+    let mut horizon = (ByteSteps::MAX, 0);
+
+    for item in stream {
+        let j = item.map_err(|_| JobError {
+            message: String::from("The underlying job stream reported an error."),
+            culprit: Job::new(),
+        })?;
+        if let Some(message) = validity_error(&j) {
+            return Err(JobError {
+                message: String::from(message),
+                culprit: j,
+            });
+        }
+
+        if j.birth < horizon.0 { horizon.0 = j.birth; }
+        if j.death > horizon.1 { horizon.1 = j.death; }
+        res.push(Arc::new(j));
+    }
+
+    Ok((res, horizon))
+}
+
 /// Forms Theorem 2's R_i groups. 
 #[inline(always)]
 pub fn split_ris(jobs: JobSet, pts: &[ByteSteps]) -> Vec<JobSet> {
@@ -136,10 +166,99 @@ pub fn get_load(jobs: &JobSet) -> ByteSteps {
     max
 }
 
+/// Sibling to [`get_load`], over the same birth/death sweep: instead of
+/// keeping only the peak, returns the whole step function of live bytes
+/// versus time--one `(time, running_total)` entry per distinct event
+/// boundary, coalescing events that land on the same instant into a
+/// single entry for that instant.
+#[inline(always)]
+pub fn get_load_profile(jobs: &JobSet) -> Vec<(ByteSteps, ByteSteps)> {
+    let mut res: Vec<(ByteSteps, ByteSteps)> = vec![];
+    let mut running = 0;
+    let mut evts = get_events(jobs);
+    while let Some(evt) = evts.pop() {
+        match evt.evt_t {
+            EventKind::Birth    => { running += evt.job.size; },
+            EventKind::Death    => {
+                if let Some(v) = running.checked_sub(evt.job.size) {
+                    running = v;
+                } else {
+                    panic!("Almost overflowed load!");
+                }
+            }
+        }
+        match res.last_mut() {
+            Some((last_t, last_running)) if *last_t == evt.time => { *last_running = running; },
+            _ => res.push((evt.time, running)),
+        }
+    }
+
+    res
+}
+
+/// Extracts the maximal interval(s) `[start, end)` of `profile` (as
+/// returned by [`get_load_profile`]) during which `running` sits at its
+/// peak--i.e. where memory pressure is at its worst, and thus where a
+/// caller deciding on spills or reorderings has the least slack to work
+/// with.
+#[inline(always)]
+pub fn get_peak_intervals(profile: &[(ByteSteps, ByteSteps)]) -> Vec<(ByteSteps, ByteSteps)> {
+    let max = profile.iter()
+        .map(|(_, running)| *running)
+        .max()
+        .unwrap_or(0);
+
+    profile.iter()
+        .zip(profile.iter().skip(1))
+        .filter(|((_, running), _)| *running == max)
+        .map(|((start, _), (end, _))| (*start, *end))
+        .collect()
+}
+
 pub fn get_total_originals_boxed(jobs: &JobSet) -> u32 {
     jobs.iter().fold(0, |sum, j| sum + j.originals_boxed)
 }
 
+/// Builds a fresh [InterferenceGraph] and [PlacedJobRegistry] over
+/// `jobs`, via the same birth/death sweep [prelude_analysis](crate::analyze::prelude_analysis)
+/// uses internally.
+///
+/// Exposed standalone so that callers needing their own, independent
+/// `Rc`-based registry--e.g. one worker among several squeezing the
+/// same jobs in parallel, where sharing `Cell` offsets would race--can
+/// rebuild one from `jobs` alone, which (being `Arc`-backed) is safe to
+/// share across threads.
+#[inline(always)]
+pub fn build_ig_registry(jobs: &JobSet) -> (InterferenceGraph, PlacedJobRegistry) {
+    let mut ig: InterferenceGraph = HashMap::new();
+    let mut registry: PlacedJobRegistry = HashMap::new();
+    let mut live: PlacedJobRegistry = HashMap::new();
+
+    let mut evts = get_events(jobs);
+    while let Some(e) = evts.pop() {
+        match e.evt_t {
+            EventKind::Birth   => {
+                let init_vec: PlacedJobSet = live.values()
+                    .cloned()
+                    .collect();
+                let new_entry = Rc::new(PlacedJob::new(e.job.clone()));
+                ig.insert(e.job.id, init_vec);
+                registry.insert(e.job.id, new_entry.clone());
+                for (_, j) in &live {
+                    let vec_handle = ig.get_mut(&j.descr.id).unwrap();
+                    vec_handle.push(new_entry.clone());
+                }
+                live.insert(e.job.id, new_entry);
+            },
+            EventKind::Death    => {
+                live.remove(&e.job.id);
+            },
+        }
+    };
+
+    (ig, registry)
+}
+
 /// Self-explanatory. Each [JobSet] of the returned vector
 /// is an IGC row.
 #[inline(always)]
@@ -1,12 +1,29 @@
 //! Welcome to `idealloc`!
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// The placement core (this module, `job`, `instance`, `jobset`, the
+// non-parsing parts of `helpe`) only ever needs heap allocation, so
+// it can run on targets with no OS--e.g. embedded within a bare-metal
+// IREE runtime. The `std`-gated pieces (file-based `JobGen`s, timing,
+// the `clap` binaries) stay out of scope on such targets. `algo` and
+// `analyze` carry no such gate: they use `Instant`, `println!` and
+// Rayon directly, so the crate as a whole does not build with `std` off.
+#[cfg(not(feature = "std"))]
+extern crate alloc;
 
 mod job;
 mod instance;
 mod analyze;
 
 pub mod algo;
+pub mod arena;
 pub mod jobset;
 pub mod helpe;
+pub mod materialize;
+// Telemetry rides on `Instant`/`Write`, both of which only exist when
+// `std` is enabled--there's no bare-metal clock or sink to report to.
+#[cfg(all(feature = "telemetry", feature = "std"))]
+pub mod telemetry;
 
 pub use crate::helpe::*;
 
@@ -0,0 +1,131 @@
+use crate::helpe::*;
+
+/// Walks a finished box hierarchy (as produced by the boxing pipeline in
+/// [`crate::algo::boxing`]) top-down, propagating `parent_base +
+/// intra_box_offset` down to every *original* leaf job and rounding each
+/// leaf's final, *absolute* offset up to a multiple of its requested
+/// [`Job::alignment`]--the same rounding an allocator `Layout`'s align
+/// requirement would demand.
+///
+/// A box itself never carries an alignment requirement--only the leaves
+/// `new_box` folds together do (see [`Job::new_box`])--so only leaf
+/// placement ever rounds. Rounding a leaf up can push it past the height
+/// its enclosing box's slot was sized for; when that happens the box is
+/// grown just enough to still fit it, and since every ancestor learns its
+/// children's *actual* (possibly grown) height before deciding where its
+/// own next child goes, that growth never causes two jobs live at the
+/// same time to land in overlapping `[offset, offset + size)` ranges.
+///
+/// Each box's children are placed via the same ascending-offset gap scan
+/// `do_best_fit` uses for already-placed neighbors--keyed on interval
+/// overlap instead of an interference graph, since a box's direct
+/// contents are few enough that a linear scan is cheap--except a leaf
+/// child's candidate offset is additionally rounded up against the
+/// box's absolute base before the overlap check, so alignment padding is
+/// accounted for at the same point a collision would be.
+///
+/// Returns the concrete, absolute offset of every original job, keyed by
+/// [`Job::id`], plus the (possibly grown) footprint of `root` itself.
+pub fn materialize_offsets(root: &Job, base_offset: ByteSteps) -> (HashMap<u32, ByteSteps>, ByteSteps) {
+    let mut offsets = HashMap::new();
+    let height = pack(root, base_offset, &mut offsets);
+    (offsets, height.max(root.size))
+}
+
+/// A box's direct child, already placed in this box's local frame.
+struct Slot {
+    birth:      ByteSteps,
+    death:      ByteSteps,
+    offset:     ByteSteps,
+    height:     ByteSteps,
+}
+
+/// Places `job` at absolute offset `base` and, if `job` is a box, packs
+/// its contents into slots measured from that same absolute `base`,
+/// recording every original leaf's final, aligned offset into `offsets`.
+/// Returns the footprint actually used, which may exceed `job.size` if a
+/// leaf's alignment padding demanded it.
+fn pack(job: &Job, base: ByteSteps, offsets: &mut HashMap<u32, ByteSteps>) -> ByteSteps {
+    if job.is_original() {
+        // `base` was already rounded up to this leaf's alignment by the
+        // parent's `find_gap` call below--nothing left to do but record it.
+        offsets.insert(job.get_id(), base);
+        return job.size;
+    }
+
+    // "Big rocks first", mirroring `new_box`'s own packing convention,
+    // so the common case--one dominant child per box--lands flush
+    // against the origin instead of behind smaller siblings.
+    let mut children: Vec<&Arc<Job>> = job.contents.as_ref().unwrap().iter().collect();
+    children.sort_unstable_by(|a, b| b.size.cmp(&a.size).then(a.birth.cmp(&b.birth)));
+
+    let mut placed: Vec<Slot> = vec![];
+    let mut max_end = 0;
+    for child in children {
+        // Only a leaf child's own placement needs to honor an alignment;
+        // a box never does (see `materialize_offsets`'s doc comment).
+        let alignment = if child.is_original() { child.get_alignment() } else { None };
+        let local_offset = find_gap(&placed, base, child.birth, child.death, child.size, alignment);
+
+        // Children are materialized *after* being placed, so a nested
+        // box that grows past its declared `size` while aligning its own
+        // leaves reports its *true* height back to `placed`, keeping
+        // every later sibling's gap scan honest.
+        let child_height = pack(child, base + local_offset, offsets);
+
+        max_end = max_end.max(local_offset + child_height);
+        placed.push(Slot {
+            birth:  child.birth,
+            death:  child.death,
+            offset: local_offset,
+            height: child_height,
+        });
+    }
+
+    max_end
+}
+
+/// Finds the lowest local offset at which a job spanning `[birth, death)`
+/// and needing `height` bytes can sit without overlapping any
+/// already-placed, time-overlapping sibling--the same ascending-offset
+/// scan `do_best_fit` runs, minus the interference graph (a box's direct
+/// children are few). When `alignment` is set, the candidate is rounded
+/// up against the enclosing box's absolute `base` before each overlap
+/// check, so a rounding bump that lands inside a later sibling's slot is
+/// caught the same way an unaligned collision would be.
+fn find_gap(
+    placed:     &[Slot],
+    base:       ByteSteps,
+    birth:      ByteSteps,
+    death:      ByteSteps,
+    height:     ByteSteps,
+    alignment:  Option<ByteSteps>,
+) -> ByteSteps {
+    let mut overlapping: Vec<&Slot> = placed.iter()
+        .filter(|s| s.birth < death && birth < s.death)
+        .collect();
+    overlapping.sort_unstable_by_key(|s| s.offset);
+
+    let mut candidate = align_up(base, alignment) - base;
+    let mut i = 0;
+    while i < overlapping.len() {
+        let s = overlapping[i];
+        if s.offset >= candidate + height {
+            break;
+        }
+        if candidate < s.offset + s.height {
+            candidate = align_up(base + s.offset + s.height, alignment) - base;
+            continue;
+        }
+        i += 1;
+    }
+    candidate
+}
+
+#[inline(always)]
+fn align_up(offset: ByteSteps, alignment: Option<ByteSteps>) -> ByteSteps {
+    match alignment {
+        Some(a) if a > 0 && offset % a != 0 => (offset / a + 1) * a,
+        _ => offset,
+    }
+}
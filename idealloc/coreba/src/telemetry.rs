@@ -0,0 +1,75 @@
+use crate::helpe::*;
+
+/// One row of the boxing/squeezing loop's convergence time-series.
+///
+/// Mirrors the quantities [`BACtrl`] and the iterative loop in
+/// [`crate::algo::idealloc`] already track, just surfaced instead of
+/// discarded after every iteration.
+pub struct IterationMetrics {
+    pub iteration:          u32,
+    /// Wall-clock time elapsed since the previous row, in microseconds.
+    pub elapsed_micros:     u128,
+    pub best_opt:           ByteSteps,
+    pub real_load:          ByteSteps,
+    /// How many jobs were re-squeezed to reach `best_opt` this iteration.
+    pub times_resqueezed:   usize,
+    pub epsilon:            f64,
+    pub mu_lim:             f64,
+}
+
+/// Emits one [IterationMetrics] row per iteration as a CSV, with a
+/// stable header, to a caller-supplied [Write] sink.
+///
+/// Construct once right before the iterative loop starts, then call
+/// [`tick`](TelemetrySink::tick) followed by
+/// [`record`](TelemetrySink::record) at the end of every iteration--a
+/// run's rows can then be post-processed to plot convergence and see
+/// which phase (Lemma 1 strip-boxing vs best-fit squeezing) dominates.
+pub struct TelemetrySink {
+    sink:           Box<dyn Write>,
+    last_tick:      Instant,
+    header_written: bool,
+}
+
+impl TelemetrySink {
+    pub fn new(sink: Box<dyn Write>) -> Self {
+        Self {
+            sink,
+            last_tick:      Instant::now(),
+            header_written: false,
+        }
+    }
+
+    /// Resets the per-iteration clock, returning the time elapsed
+    /// since the previous call (or since the sink was created).
+    pub fn tick(&mut self) -> core::time::Duration {
+        let now = Instant::now();
+        let delta = now.duration_since(self.last_tick);
+        self.last_tick = now;
+        delta
+    }
+
+    /// Appends one row, writing the CSV header first if this is the
+    /// sink's first call.
+    pub fn record(&mut self, m: &IterationMetrics) -> std::io::Result<()> {
+        if !self.header_written {
+            writeln!(
+                self.sink,
+                "iteration,elapsed_micros,best_opt,real_load,times_resqueezed,epsilon,mu_lim"
+            )?;
+            self.header_written = true;
+        }
+        writeln!(
+            self.sink,
+            "{},{},{},{},{},{},{}",
+            m.iteration,
+            m.elapsed_micros,
+            m.best_opt,
+            m.real_load,
+            m.times_resqueezed,
+            m.epsilon,
+            m.mu_lim,
+        )?;
+        self.sink.flush()
+    }
+}
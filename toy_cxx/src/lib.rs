@@ -1,7 +1,21 @@
+// This whole crate is the timer + `cxx` FFI bridge into `coreba`'s
+// placement core. `std::time::Instant` and `cxx`'s C++ interop don't
+// exist on a bare-metal target, so there's nothing to link against
+// there--the bridge itself is gated behind a default-on `std` feature,
+// mirroring `coreba`'s own `std`/`no_std`+`alloc` split, leaving a
+// no_std build of this crate with no public surface.
+#![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
 use std::time::{Instant, Duration};
+#[cfg(feature = "std")]
 use cxx::CxxVector;
-use ffi::UnplacedSlice;
+#[cfg(feature = "std")]
+use ffi::{UnplacedSlice, PlacementRequest, PlacedSlice, PlacementResult};
 
+#[cfg(feature = "std")]
 #[cxx::bridge]
 mod ffi {
     /// A memory buffer in need of
@@ -13,19 +27,59 @@ mod ffi {
         pub align:  i64,
     }
 
+    /// Tunables for a single [`place_slices`] call, letting a C++ caller
+    /// trade runtime for packing quality instead of being stuck with
+    /// whatever was hardcoded on the Rust side.
+    struct PlacementRequest {
+        /// Worst-case fragmentation `idealloc` is allowed to settle for,
+        /// as a multiple of the true load (e.g. `1.0` accepts no slack
+        /// at all above it)--see [`coreba::algo::idealloc`]'s
+        /// `worst_case_frag`.
+        pub epsilon:        f64,
+        /// RNG seed for `idealloc`'s randomized search (see
+        /// `coreba::seed_rng`). It fixes the sequence of values drawn,
+        /// but boxing draws them from parallel workers in no fixed order,
+        /// so two calls with the same `seed` can still place differently.
+        /// `0` is not special-cased: pass it like any other seed.
+        pub seed:           u64,
+        /// Maximum number of BA trials allowed to outperform the last
+        /// best placement--see `idealloc`'s `max_lives`.
+        pub max_lives:      u32,
+        /// Width of each parallel wave of those trials--see `idealloc`'s
+        /// `batch_width`.
+        pub batch_width:    u32,
+    }
+
+    /// One slice's outcome: its assigned offset, and whether the
+    /// alignment it requested was actually honored there.
+    struct PlacedSlice {
+        pub offset:             i64,
+        pub alignment_honored:  bool,
+    }
+
+    /// Everything a C++ caller needs back from a placement: one
+    /// [`PlacedSlice`] per input slice (in the same order), plus the
+    /// total footprint--the peak bytes needed--it must reserve.
+    struct PlacementResult {
+        pub slices:     Vec<PlacedSlice>,
+        pub makespan:   i64,
+    }
+
     extern "Rust" {
         type Clock;
         fn timer_start() -> Box<Clock>;
         fn timer_end(clk: Box<Clock>);
-        fn place_slices(data: &CxxVector<UnplacedSlice>) -> Vec<i64>;
+        fn place_slices(data: &CxxVector<UnplacedSlice>, req: PlacementRequest) -> PlacementResult;
     }
 }
 
 /// Wraps [Instant] so as to be usable by [cxx].
+#[cfg(feature = "std")]
 struct Clock {
     heart: Instant,
 }
 
+#[cfg(feature = "std")]
 impl Clock {
     fn new() -> Self {
         Clock {
@@ -43,34 +97,42 @@ impl Clock {
 
 /// Creates a new [Clock] and wraps it around a [Box],
 /// so as to be passable across [cxx]'s FFI bridge.
+#[cfg(feature = "std")]
 fn timer_start() -> Box<Clock> {
     Box::new(Clock::new())
 }
 
 /// Consumes a boxed [Clock] and prints the time elapsed
 /// since its creation to stdout.
+#[cfg(feature = "std")]
 fn timer_end(clk: Box<Clock>) {
     println!("Allocation time: {} μs", clk.tick().as_micros());
 }
 
+#[cfg(feature = "std")]
 use coreba::*;
 
 /// Gatekeeper to `idealloc`.
-fn place_slices(data: &CxxVector<UnplacedSlice>) -> Vec<i64> {
-    // Offsets will be written here.
-    let mut res = vec![0; data.len()];
+#[cfg(feature = "std")]
+fn place_slices(data: &CxxVector<UnplacedSlice>, req: PlacementRequest) -> PlacementResult {
+    // Offsets (and alignments, to check against afterward) will be
+    // written here.
+    let mut offsets = vec![0; data.len()];
+    let mut alignments: Vec<Option<ByteSteps>> = vec![None; data.len()];
 
     let mut dirty_jobs: JobSet = vec![];
     for (id, s) in data
         .iter()
         .enumerate() {
+        let alignment = Some(s.align as ByteSteps);
+        alignments[id] = alignment;
         dirty_jobs.push(
             Arc::new(Job {
                 size:               s.size as ByteSteps,
                 birth:              s.start as ByteSteps,
                 death:              s.end as ByteSteps + 2,
                 req_size:           s.size as ByteSteps,
-                alignment:          Some(s.align as ByteSteps),
+                alignment,
                 contents:           None,
                 originals_boxed:    0,
                 id:                 id as u32,
@@ -119,11 +181,32 @@ fn place_slices(data: &CxxVector<UnplacedSlice>) -> Vec<i64> {
     };
 
     // In theory, we're ready.
-    let (reg, _makespan) = coreba::algo::idealloc(coreba::jobset::init(idealloc_inp).unwrap(), 1.0, 0, 3);
+    coreba::seed_rng(Some(req.seed));
+    let (reg, makespan) = coreba::algo::idealloc(
+        coreba::jobset::init(idealloc_inp).unwrap(),
+        req.epsilon,
+        0,
+        req.max_lives,
+        req.batch_width,
+        #[cfg(all(feature = "telemetry", feature = "std"))]
+        None,
+    );
 
     for (id, pj) in &reg {
-        res[*id as usize] = pj.offset.get() as i64;
+        offsets[*id as usize] = pj.offset.get() as i64;
     }
 
-    res
-}
\ No newline at end of file
+    PlacementResult {
+        slices: offsets.into_iter()
+            .zip(alignments)
+            .map(|(offset, alignment)| PlacedSlice {
+                offset,
+                alignment_honored: match alignment {
+                    Some(a) => offset as ByteSteps % a == 0,
+                    None => true,
+                },
+            })
+            .collect(),
+        makespan: makespan as i64,
+    }
+}